@@ -0,0 +1,236 @@
+use geo::orient::{Orient, Direction};
+use geo::{BoundingRect, CoordinatePosition, GeoFloat, Intersects};
+use geo::coordinate_position::CoordPos;
+use geo::line_intersection::{line_intersection, LineIntersection};
+use geo_types::{Line, LineString, Polygon};
+
+use crate::util::*;
+use crate::error::BufferError;
+use crate::validation::check_polygon;
+
+/// Returns `true` if the vertex `cv` lies on the line through `lv` and `rv`, i.e. the directions of the edges
+/// `lv -> cv` and `cv -> rv` are either the same or opposite.
+fn is_collinear<T: GeoFloat>(lv: Coordinate<T>, cv: Coordinate<T>, rv: Coordinate<T>, eps: f64) -> bool{
+    let d1 = cv - lv;
+    let d2 = rv - cv;
+    feq(d1.outer_product(&d2) / (d1.norm() * d2.norm()), T::zero(), eps)
+}
+
+/// Returns the cleaned ring of the given ring, or `None` if the ring is degenerate.
+///
+/// The result has no repeated points nor collinear vertices and is closed. A ring is degenerate if fewer than three vertices remain.
+fn clean_ring<T: GeoFloat>(ring: &LineString<T>, eps: f64) -> Option<LineString<T>>{
+    let mut crdv: Vec<Coordinate<T>> = Vec::with_capacity(ring.0.len());
+    for c in &ring.0{
+        let c: Coordinate<T> = (*c).into();
+        if crdv.last().is_some_and(|l| l.eq_within(&c, eps)) {continue;}
+        crdv.push(c);
+    }
+    while crdv.len() > 1 && crdv[0].eq_within(&crdv[crdv.len()-1], eps) {crdv.pop();}
+    // Removing a vertex may make one of its neighbors collinear, thus repeat until no vertex is removed.
+    let mut removed = true;
+    while removed && crdv.len() >= 3{
+        removed = false;
+        let mut i = 0;
+        while i < crdv.len() && crdv.len() >= 3{
+            let len = crdv.len();
+            if is_collinear(crdv[(i+len-1)%len], crdv[i], crdv[(i+1)%len], eps) {
+                crdv.remove(i);
+                removed = true;
+            }
+            else {i += 1;}
+        }
+    }
+    if crdv.len() < 3 {return None;}
+    let mut ls = LineString::from(crdv.into_iter().map(|c| c.get_val()).collect::<Vec<_>>());
+    ls.close();
+    Some(ls)
+}
+
+/// Returns the cleaned polygons of the given polygons, which can be passed to the straight skeleton construction safely.
+///
+/// Every ring is cleaned by removing repeated points and collinear vertices and closing it. Degenerate holes are dropped,
+/// and a polygon is dropped if its exterior ring is degenerate. Finally, the exterior ring of each polygon is oriented counter-clockwise
+/// and the interior rings are oriented clockwise, as the straight skeleton construction assumes.
+pub(crate) fn clean_polygons<T: GeoFloat>(input_polygon_vector: &[Polygon<T>], eps: f64) -> Vec<Polygon<T>>{
+    input_polygon_vector.iter().filter_map(|p| {
+        let exterior = clean_ring(p.exterior(), eps)?;
+        let interiors = p.interiors().iter().filter_map(|ls| clean_ring(ls, eps)).collect();
+        Some(Polygon::new(exterior, interiors).orient(Direction::Default))
+    }).collect()
+}
+
+/// Returns `true` if the interiors of the given polygons intersect each other, or the polygons share a part of their boundaries.
+///
+/// The rings are tested segment by segment rather than by `relate` of `geo`, which may panic on invalid polygons.
+fn is_overlapping<T: GeoFloat>(p1: &Polygon<T>, p2: &Polygon<T>) -> bool{
+    match (p1.bounding_rect(), p2.bounding_rect()){
+        (Some(r1), Some(r2)) if r1.intersects(&r2) => {},
+        _ => return false,
+    }
+    let lines = |p: &Polygon<T>| std::iter::once(p.exterior()).chain(p.interiors()).flat_map(|ls| ls.lines()).collect::<Vec<_>>();
+    let (lines1, lines2) = (lines(p1), lines(p2));
+    for &l1 in &lines1{
+        for &l2 in &lines2{
+            match line_intersection(l1, l2){
+                Some(LineIntersection::SinglePoint { is_proper: true, .. }) => return true,
+                Some(LineIntersection::Collinear { intersection }) if intersection.start != intersection.end => return true,
+                _ => {},
+            }
+        }
+    }
+    // The boundaries touch each other at points at most, thus the interiors intersect if and only if a part of a boundary lies inside
+    // the other polygon. The midpoints are tested as well, since every vertex may lie on the other boundary.
+    let inside = |p: &Polygon<T>, lines: &[Line<T>]| lines.iter().any(|l| {
+        [l.start, (l.start + l.end)/float(2.)].iter().any(|c| p.coordinate_position(c) == CoordPos::Inside)
+    });
+    inside(p2, &lines1) || inside(p1, &lines2)
+}
+
+/// Returns the polygons whose overlapping members (or members sharing an edge) are merged into their union, so that the result polygons
+/// have disjoint interiors and touch each other at finitely many points at most.
+/// The members which do not overlap any other member are returned as they are.
+///
+/// Returns [BufferError::InvalidInput] if a member whose bounding box intersects that of another member is invalid. (See [check_polygon()].)
+pub(crate) fn merge_overlapping<T: BufferFloat>(polygons: Vec<Polygon<T>>, eps: f64) -> Result<Vec<Polygon<T>>, BufferError>{
+    // Groups the overlapping members by a disjoint-set forest.
+    fn find(parent: &mut [usize], x: usize) -> usize{
+        if parent[x] != x {parent[x] = find(parent, parent[x]);}
+        parent[x]
+    }
+    // Only the members which may overlap another member go through the `relate` and boolean operations of `geo`.
+    let rects = polygons.iter().map(|p| p.bounding_rect()).collect::<Vec<_>>();
+    for (i, p) in polygons.iter().enumerate(){
+        let near = rects.iter().enumerate().any(|(j, r)| j != i && matches!((rects[i], r), (Some(a), Some(b)) if a.intersects(b)));
+        if near {check_polygon(p)?;}
+    }
+    let mut parent = (0..polygons.len()).collect::<Vec<_>>();
+    for i in 0..polygons.len(){
+        for j in i+1..polygons.len(){
+            if is_overlapping(&polygons[i], &polygons[j]) {
+                let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                parent[rj] = ri;
+            }
+        }
+    }
+    let mut groups: Vec<Vec<Polygon<T>>> = vec![Vec::new(); polygons.len()];
+    for (i, p) in polygons.into_iter().enumerate(){
+        let r = find(&mut parent, i);
+        groups[r].push(p);
+    }
+    let mut ret = Vec::new();
+    for group in groups{
+        if group.len() > 1 {ret.extend(clean_polygons(&union_polygons(group).0, eps));}
+        else {ret.extend(group);}
+    }
+    Ok(ret)
+}
+
+/// Returns the distance by which a vertex touching another ring is moved in [separate_touching()], which is a few times the tolerance
+/// so that the moved vertex is told apart from the vertex it touches.
+fn separation<T: GeoFloat>(eps: f64) -> T{
+    float(4.*eps)
+}
+
+/// Returns `true` if `c` lies on the (closed) segment from `a` to `b`. The decision is exact. (See [orient2d()].)
+fn on_segment<T: GeoFloat>(c: Coordinate<T>, a: Coordinate<T>, b: Coordinate<T>) -> bool{
+    orient2d(a, b, c) == 0
+        && T::min(a.0, b.0) <= c.0 && c.0 <= T::max(a.0, b.0)
+        && T::min(a.1, b.1) <= c.1 && c.1 <= T::max(a.1, b.1)
+}
+
+/// Returns the polygons where every vertex touching another ring, or a non-adjacent part of its own ring, is moved slightly
+/// along its incoming edge. That is, polygons touching each other at a vertex are pulled apart, and a hole touching its shell
+/// (or another hole) is detached, so that the straight skeleton construction never meets coincident vertices.
+///
+/// Only the vertices lying exactly on another segment are moved, thus the rings which merely come close to each other are kept
+/// as they are. The segments are swept from left to right, so that each vertex is tested only against the segments whose x-ranges
+/// contain it.
+///
+/// Each vertex is moved by [separation()], but by a quarter of its incoming edge at most, thus the buffer deviates by a few times
+/// the tolerance at most around such vertices. Returns the largest distance moved as well, or `None` if no vertex is moved.
+/// Moving the vertices along their edges rather than their bisectors keeps the bisectors of the touching vertices apart, which would
+/// otherwise collide head-on at the same instant.
+pub(crate) fn separate_touching<T: GeoFloat>(polygons: Vec<Polygon<T>>, eps: f64) -> (Vec<Polygon<T>>, Option<f64>){
+    // Each ring is given with the vertices without the closing one.
+    let rings = polygons.iter().flat_map(|p| std::iter::once(p.exterior()).chain(p.interiors())).map(|ls| {
+        let mut crdv = ls.0.iter().map(|c| Coordinate::from(*c)).collect::<Vec<_>>();
+        crdv.pop();
+        crdv
+    }).collect::<Vec<_>>();
+    // Both the vertices and the segments are given by the index of the ring and the index in the ring, where the segment of an index
+    // starts at the vertex of the same index.
+    let segment = |(r, j): (usize, usize)| (rings[r][j], rings[r][(j+1)%rings[r].len()]);
+    let min_x = |s: (usize, usize)| {let (a, b) = segment(s); T::min(a.0, b.0)};
+    let max_x = |s: (usize, usize)| {let (a, b) = segment(s); T::max(a.0, b.0)};
+    let cmp = |x: T, y: T| x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal);
+    let mut segments = rings.iter().enumerate().flat_map(|(r, crdv)| (0..crdv.len()).map(move |j| (r, j))).collect::<Vec<_>>();
+    let mut vertices = segments.clone();
+    segments.sort_by(|&s, &t| cmp(min_x(s), min_x(t)));
+    vertices.sort_by(|&(r, i), &(s, k)| cmp(rings[r][i].0, rings[s][k].0));
+    let mut active = Vec::new();
+    let mut next = 0;
+    let mut moved = rings.clone();
+    let mut max_distance: Option<T> = None;
+    for (r, i) in vertices{
+        let (crdv, c) = (&rings[r], rings[r][i]);
+        let len = crdv.len();
+        while next < segments.len() && min_x(segments[next]) <= c.0 {active.push(segments[next]); next += 1;}
+        active.retain(|&s| max_x(s) >= c.0);
+        let touches = active.iter().any(|&(r2, j)| {
+            let incident = r2 == r && (j == i || (j+1)%len == i);
+            let (a, b) = segment((r2, j));
+            !incident && on_segment(c, a, b)
+        });
+        if !touches {continue;}
+        let mut u = Ray::new(c, crdv[(i+len-1)%len]);
+        let distance = T::min(separation(eps), u.angle.norm()/float(4.));
+        u.normalize();
+        moved[r][i] = c + u.angle*distance;
+        max_distance = Some(max_distance.map_or(distance, |x| T::max(x, distance)));
+    }
+    let mut iter = moved.into_iter().map(|crdv| {
+        let mut ls = LineString::from(crdv.into_iter().map(|c| c.get_val()).collect::<Vec<_>>());
+        ls.close();
+        ls
+    });
+    let ret = polygons.iter().map(|p| {
+        let exterior = iter.next().unwrap_or_else(|| LineString::new(vec![]));
+        let interiors = (0..p.interiors().len()).filter_map(|_| iter.next()).collect();
+        Polygon::new(exterior, interiors)
+    }).collect();
+    (ret, max_distance.map(to_f64))
+}
+
+/// Returns the polygons whose vertices are moved by `distance` in pseudo-random but deterministic directions, which differ for
+/// each `seed`.
+///
+/// A tiny perturbation resolves most of the degenerate configurations which the straight skeleton construction fails on, e.g. several
+/// events which almost coincide but are not detected as simultaneous ones.
+pub(crate) fn perturb_polygons<T: GeoFloat>(polygons: &[Polygon<T>], distance: f64, seed: usize) -> Vec<Polygon<T>>{
+    // The directions follow the golden angle, so that nearby vertices are moved in different directions.
+    const GOLDEN_ANGLE: f64 = 2.399963229728653;
+    let mut k = seed;
+    let mut perturb_ring = |ring: &LineString<T>| {
+        let len = ring.0.len().saturating_sub(1);
+        let mut ls = LineString::from(ring.0[..len].iter().map(|c| {
+            k += 1;
+            let a = GOLDEN_ANGLE*(k as f64);
+            geo_types::coord!{x: c.x + float(distance*a.cos()), y: c.y + float(distance*a.sin())}
+        }).collect::<Vec<_>>());
+        ls.close();
+        ls
+    };
+    polygons.iter().map(|p| {
+        let exterior = perturb_ring(p.exterior());
+        let interiors = p.interiors().iter().map(&mut perturb_ring).collect();
+        Polygon::new(exterior, interiors)
+    }).collect()
+}
+
+/// Returns the polygons which can be passed to the straight skeleton construction, i.e. the cleaned polygons whose overlapping
+/// members are merged and touching vertices are separated, with the largest distance by which a touching vertex is moved.
+/// Returns [BufferError::InvalidInput] if an invalid member would be merged. (See [clean_polygons()], [merge_overlapping()] and [separate_touching()].)
+pub(crate) fn prepare_polygons<T: BufferFloat>(input_polygon_vector: &[Polygon<T>], eps: f64) -> Result<(Vec<Polygon<T>>, Option<f64>), BufferError>{
+    Ok(separate_touching(merge_overlapping(clean_polygons(input_polygon_vector, eps), eps)?, eps))
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use geo::{CoordsIter, GeoFloat};
+
+/// This enumeration represents the reasons why a geometry could not be buffered.
+///
+/// It is returned by the fallible functions of this crate such as [try_buffer_polygon][crate::try_buffer_polygon] and
+/// [Buffer::try_buffer_with][crate::Buffer::try_buffer_with]. The infallible counterparts panic with the message of this error instead.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BufferError{
+    /// The given geometry or parameter cannot be buffered, e.g. it contains non-finite coordinates.
+    InvalidInput(String),
+    /// A numerical computation produced a non-finite value, typically because of (nearly) degenerate input such as
+    /// repeated consecutive points.
+    NumericalFailure(String),
+    /// The internal data structures became inconsistent while constructing the straight skeleton or assembling the result.
+    /// This is a bug of this crate or of its dependencies.
+    InternalInconsistency(String),
+}
+
+impl fmt::Display for BufferError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match self{
+            BufferError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            BufferError::NumericalFailure(msg) => write!(f, "numerical failure: {msg}"),
+            BufferError::InternalInconsistency(msg) => write!(f, "internal inconsistency: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for BufferError{}
+
+/// Returns an error if the given value is not finite. `what` describes the value in the error message.
+pub(crate) fn ensure_finite<T: GeoFloat>(value: T, what: &str) -> Result<T, BufferError>{
+    if value.is_finite() {Ok(value)}
+    else {Err(BufferError::NumericalFailure(format!("{what} is {value:?}")))}
+}
+
+/// Returns an error if the given geometry has a non-finite coordinate or the given distance is not finite.
+pub(crate) fn check_input<T: GeoFloat, G: CoordsIter<Scalar = T>>(input_geometry: &G, distance: T) -> Result<(), BufferError>{
+    if !distance.is_finite() {
+        return Err(BufferError::InvalidInput(format!("the buffer distance is {distance:?}")));
+    }
+    if let Some(c) = input_geometry.coords_iter().find(|c| !c.x.is_finite() || !c.y.is_finite()) {
+        return Err(BufferError::InvalidInput(format!("the coordinate ({:?}, {:?}) is not finite", c.x, c.y)));
+    }
+    Ok(())
+}
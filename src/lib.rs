@@ -4,11 +4,42 @@
 //! This crate can handle simple polygons properly as well as non-convex polygons, (valid) sets of polygons, and polygons with one or more holes.
 //! Note that each method assumes **valid** primitives as a parameter, but [Polygon][Polygon module]/[MultiPolygon][MultiPolygon module] modules
 //! *do not* enforce this validity automatically nor does this crate. (See more details on 'Validity' section in [Polygon][Polygon module]/[MultiPolygon][MultiPolygon module]
-//!  and [OGC standards].)
+//!  and [OGC standards].) You can check the validity of the input in advance with [validate_for_buffer()], which reports each invalidity with its location.
+//! 
+//! Repeated points and collinear vertices are removed and degenerate rings are dropped automatically before building a straight skeleton.
+//! Overlapping members of a multi-polygon (or members sharing an edge) are merged into their union as well, thus the result is the buffer of the union.
+//! Members touching each other at a vertex, and holes touching their shell or each other, are pulled apart by a few times the tolerance,
+//! which is reported in [BufferReport::separation].
+//! 
+//! Every input is translated to a local origin and scaled into a well-conditioned range before it is buffered (or its straight skeleton is built),
+//! thus buffering parcels in projected coordinates (e.g. EPSG:3857 meters) behaves like buffering unit squares. The tolerance used to
+//! compare points and times is relative to the extent of the input by default, and can be set explicitly by [BufferOptions::tolerance].
+//! 
+//! Every function is generic over the coordinate type of the input (see [BufferFloat]), thus `Polygon<f32>` is buffered into `MultiPolygon<f32>`
+//! without a round trip through `f64`. The default tolerance is scaled to the precision of the type, e.g. it is about `2.3e-5` for `f32`.
+//! Since 0.3.0, the coordinate type of a geometry built from float literals may need an annotation, e.g. `let p: Polygon = ...`,
+//! if float methods are called on its result.
 //! 
 //! This crate use a [straight skeleton] to buffer (multi-)polygons. You can also get a straight skeleton separately by proper methods.
+//! [StraightSkeleton] exposes its nodes, arcs and event times, the input vertex and edges of each wavefront vertex, and the face swept by each input edge.
+//! [RoofMesh] lifts these faces into the 3D mesh of a hip roof over a building footprint, which can be exported to OBJ and STL.
+//! Footprint edges can be marked as gables (see [Gable]) to make gable, Dutch gable and saltbox roofs.
+//! 
+//! With the `exact` cargo feature, the straight skeleton of (multi-)polygons is constructed with exact rational arithmetic.
+//! It is much slower, but the skeleton is bit-for-bit reproducible, and the simultaneous events are detected exactly if every edge has
+//! a rational length (e.g. the axis-parallel edges). Otherwise the direction of the edge is rounded to a rational unit vector, thus the events
+//! which coincide only for the irrational directions (e.g. of a regular octagon) may be resolved as separate events very close to each other.
+//! The size of the rational numbers grows with the number of events and each reflex vertex is tested against every wavefront edge,
+//! thus the feature is meant for small inputs with degenerate events rather than for large ones.
+//! 
+//! The viable geometric primitives are [Polygon][Polygon module], [MultiPolygon][MultiPolygon module], `LineString`, `MultiLineString`, `Line`,
+//! `Point`, `MultiPoint`, `Rect` and `Triangle`. The ends of buffered line strings can be flat, square or round. (See [EndCapStyle].)
 //! 
-//! For now, the only viable geometric primitives are [Polygon][Polygon module] and [MultiPolygon][MultiPolygon module] (the rest of the primitives will be added as well).
+//! Each primitive implements the [Buffer] trait, thus you can also write `p1.buffer(0.2)` or `p1.buffer_with(0.2, &options)`
+//! instead of calling the functions below. (See [BufferOptions].)
+//! 
+//! The `try_buffer_*` functions (e.g. [try_buffer_polygon()]) and [Buffer::try_buffer_with] return a [BufferError] instead of
+//! panicking when the given geometry cannot be buffered.
 //! 
 //! # Quick Guide
 //! 
@@ -116,11 +147,14 @@
 //! # Notes
 //! 
 //! It has been shown that the algorithm presented in this paper is incorrect.[^note3] Thus we slightly modified the algorithm for some edge cases.
+//! In particular, all the wavefront vertices and edges which meet at the same point at the same time (e.g. several reflex vertices
+//! hitting the same point, or a reflex vertex hitting another one) are resolved together as a single event, so that symmetric
+//! shapes such as regular stars, staircases and building footprints get correct skeletons.
 //! 
 //! 
 //! [GeoRust]: https://georust.org
-//! [Polygon module]: https://docs.rs/geo/0.24.1/geo/geometry/struct.Polygon.html
-//! [MultiPolygon module]: https://docs.rs/geo/0.24.1/geo/geometry/struct.MultiPolygon.html
+//! [Polygon module]: https://docs.rs/geo/0.29.3/geo/geometry/struct.Polygon.html
+//! [MultiPolygon module]: https://docs.rs/geo/0.29.3/geo/geometry/struct.MultiPolygon.html
 //! [OGC standards]: https://www.ogc.org/standard/sfa/
 //! [straight skeleton]: https://en.wikipedia.org/wiki/Straight_skeleton
 //! [^note1]: Felkel, Petr; Obdržálek, Štěpán (1998), *"Straight skeleton implementation"*, SCCG 98: Proceedings of the 14th Spring Conference on Computer Graphics, pp. 210–218.
@@ -134,16 +168,290 @@
 
 mod priority_queue;
 mod vertex_queue;
+mod line_string;
+mod point;
+mod options;
+mod error;
+mod report;
+mod cleaning;
+mod normalization;
+mod validation;
+mod roof;
 pub mod util;
 pub mod skeleton;
 
 #[doc(inline)]
-pub use util::{Coordinate, Ray};
+pub use util::{Coordinate, Ray, BufferFloat};
+pub use options::{BufferOptions, EndCapStyle, JoinStyle, ArcResolution};
+pub use error::BufferError;
+pub use report::BufferReport;
+pub use skeleton::{StraightSkeleton, SkeletonNode, SkeletonNodeKind, SkeletonArc, InputIndex};
+pub use validation::{validate_for_buffer, Invalidity, InvalidityKind};
+pub use roof::{RoofMesh, RoofOptions, Pitch, Gable};
 
 // Main functions in this module
 
-use geo_types::{Polygon, MultiPolygon, LineString};
+use geo_types::{Polygon, MultiPolygon, LineString, MultiLineString, Point, MultiPoint, Line, Rect, Triangle, Geometry, GeometryCollection};
 use skeleton::Skeleton;
+use line_string::line_string_pieces;
+use point::point_piece;
+use util::{union_polygons, float};
+use error::check_input;
+use validation::check_output;
+use normalization::{normalized, buffer_normalized};
+
+/// This trait provides the buffer operation on the geometric primitives in the [GeoRust] ecosystem, in the same manner as
+/// the algorithm traits of the `geo` crate (e.g. `Area`, `Contains`).
+/// 
+/// + `buffer()` creates miter-joint-like corners around each convex vertex and round ends of line strings. (See [BufferOptions].)
+/// + `buffer_with()` shapes the result according to the given [BufferOptions].
+/// + `try_buffer()` and `try_buffer_with()` return a [BufferError] instead of panicking if the given geometry cannot be buffered.
+/// + `try_buffer_with_report()` returns a [BufferReport] together with the result, which tells e.g. whether a fallback was used.
+/// 
+/// The absolute value of `distance` determines how distant from each edge of the original geometry to each edge of the result polygon.
+/// The sign will be:
+/// + `+` to inflate (to add paddings, make bigger) the given geometry, and,
+/// + `-` to deflate (to add margins, make smaller) the given geometry.
+/// 
+/// Note that the result is empty if `distance` is not positive and the given geometry has no interior (e.g. `Point`, `LineString`).
+/// 
+/// The winding order of the input rings does not matter. In the result, the exterior ring of each polygon is oriented counter-clockwise
+/// and the interior rings are oriented clockwise.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{Buffer, BufferOptions, JoinStyle};
+/// use geo::{Polygon, MultiPolygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]), vec![],
+/// );
+/// let p2: MultiPolygon = p1.buffer(-0.2);
+/// let p3: MultiPolygon = p1.buffer_with(0.2, &BufferOptions::new().join_style(JoinStyle::Round));
+///
+/// let expected_exterior = LineString::from(vec![(0.2, 0.2), (0.8, 0.2), (0.8, 0.8), (0.2, 0.8), (0.2, 0.2)]);
+/// assert_eq!(&expected_exterior, p2.0[0].exterior())
+/// ```
+pub trait Buffer<T: BufferFloat = f64>{
+    /// Returns the buffered (multi-)polygon of `self` with the default [BufferOptions].
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `self` cannot be buffered. Use [try_buffer()][Buffer::try_buffer] to handle the error instead.
+    fn buffer(&self, distance: T) -> MultiPolygon<T>{
+        self.buffer_with(distance, &BufferOptions::default())
+    }
+
+    /// Returns the buffered (multi-)polygon of `self` with the given [BufferOptions].
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `self` cannot be buffered. Use [try_buffer_with()][Buffer::try_buffer_with] to handle the error instead.
+    fn buffer_with(&self, distance: T, options: &BufferOptions) -> MultiPolygon<T>{
+        self.try_buffer_with(distance, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Returns the buffered (multi-)polygon of `self` with the default [BufferOptions], or a [BufferError] if `self` cannot be buffered.
+    fn try_buffer(&self, distance: T) -> Result<MultiPolygon<T>, BufferError>{
+        self.try_buffer_with(distance, &BufferOptions::default())
+    }
+
+    /// Returns the buffered (multi-)polygon of `self` with the given [BufferOptions], or a [BufferError] if `self` cannot be buffered.
+    fn try_buffer_with(&self, distance: T, options: &BufferOptions) -> Result<MultiPolygon<T>, BufferError>{
+        self.try_buffer_with_report(distance, options).map(|(res, _)| res)
+    }
+
+    /// Returns the buffered (multi-)polygon of `self` with the given [BufferOptions] and the [BufferReport] which describes how it was
+    /// computed, or a [BufferError] if `self` cannot be buffered.
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>;
+}
+
+impl<T: BufferFloat> Buffer<T> for MultiPolygon<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        check_input(self, distance)?;
+        buffer_normalized(self, distance, options, |input, distance, options, eps| {
+            let orientation = distance < T::zero();
+            let offset_distance = distance.abs();
+            Skeleton::with_skeleton_of_polygon_vector(&input.0, orientation, eps, |skel| {
+                let vq = skel.get_vertex_queue(offset_distance)?;
+                let res = match options.join_style{
+                    JoinStyle::Miter => skel.apply_vertex_queue(&vq, offset_distance, options.miter_limit),
+                    JoinStyle::Round => skel.apply_vertex_queue_rounded(&vq, offset_distance, &options.arc_resolution),
+                    JoinStyle::Bevel => skel.apply_vertex_queue_beveled(&vq, offset_distance),
+                }?;
+                check_output(&res)?;
+                Ok((res, skel.report()))
+            })
+        })
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Polygon<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        MultiPolygon::new(vec![self.clone()]).try_buffer_with_report(distance, options)
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Rect<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        self.to_polygon().try_buffer_with_report(distance, options)
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Triangle<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        self.to_polygon().try_buffer_with_report(distance, options)
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for MultiLineString<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        check_input(self, distance)?;
+        // The outline of a line string has no interior, thus its straight skeleton degenerates. (See buffer_line_string().)
+        buffer_normalized(self, distance, options, |input, distance, options, eps| {
+            let pieces = input.0.iter().flat_map(|ls| line_string_pieces(ls, distance, options, eps)).collect::<Vec<_>>();
+            Ok((union_polygons(pieces), BufferReport::default()))
+        })
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for LineString<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        MultiLineString::new(vec![self.clone()]).try_buffer_with_report(distance, options)
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Line<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        LineString::from(*self).try_buffer_with_report(distance, options)
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for MultiPoint<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        check_input(self, distance)?;
+        buffer_normalized(self, distance, options, |input, distance, options, eps| {
+            let pieces = input.0.iter().filter_map(|p| point_piece(p.0.into(), distance, options, eps)).collect();
+            Ok((union_polygons(pieces), BufferReport::default()))
+        })
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Point<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        check_input(self, distance)?;
+        buffer_normalized(self, distance, options, |input, distance, options, eps| {
+            Ok((MultiPolygon::new(point_piece(input.0.into(), distance, options, eps).into_iter().collect()), BufferReport::default()))
+        })
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for GeometryCollection<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        fn collect_helper<T: BufferFloat>(geometry: &Geometry<T>, polygons: &mut Vec<Polygon<T>>, others: &mut Vec<Geometry<T>>){
+            match geometry{
+                Geometry::Polygon(g) => polygons.push(g.clone()),
+                Geometry::MultiPolygon(g) => polygons.extend(g.0.iter().cloned()),
+                Geometry::Rect(g) => polygons.push(g.to_polygon()),
+                Geometry::Triangle(g) => polygons.push(g.to_polygon()),
+                Geometry::GeometryCollection(g) => g.0.iter().for_each(|e| collect_helper(e, polygons, others)),
+                _ => others.push(geometry.clone()),
+            }
+        }
+        check_input(self, distance)?;
+        // The members are buffered in the range of the whole collection, with the tolerance of the whole collection.
+        buffer_normalized(self, distance, options, |input, distance, options, _eps| {
+            let mut polygons = Vec::new();
+            let mut others = Vec::new();
+            input.0.iter().for_each(|e| collect_helper(e, &mut polygons, &mut others));
+            let mut pieces = Vec::new();
+            let mut report = BufferReport::default();
+            if !polygons.is_empty() {
+                let (res, r) = MultiPolygon::new(polygons).try_buffer_with_report(distance, options)?;
+                pieces.extend(res);
+                report = report.merge(r);
+            }
+            for g in &others{
+                let (res, r) = g.try_buffer_with_report(distance, options)?;
+                pieces.extend(res);
+                report = report.merge(r);
+            }
+            Ok((union_polygons(pieces), report))
+        })
+    }
+}
+
+impl<T: BufferFloat> Buffer<T> for Geometry<T>{
+    fn try_buffer_with_report(&self, distance: T, options: &BufferOptions) -> Result<(MultiPolygon<T>, BufferReport), BufferError>{
+        match self{
+            Geometry::Point(g) => g.try_buffer_with_report(distance, options),
+            Geometry::Line(g) => g.try_buffer_with_report(distance, options),
+            Geometry::LineString(g) => g.try_buffer_with_report(distance, options),
+            Geometry::Polygon(g) => g.try_buffer_with_report(distance, options),
+            Geometry::MultiPoint(g) => g.try_buffer_with_report(distance, options),
+            Geometry::MultiLineString(g) => g.try_buffer_with_report(distance, options),
+            Geometry::MultiPolygon(g) => g.try_buffer_with_report(distance, options),
+            Geometry::GeometryCollection(g) => g.try_buffer_with_report(distance, options),
+            Geometry::Rect(g) => g.try_buffer_with_report(distance, options),
+            Geometry::Triangle(g) => g.try_buffer_with_report(distance, options),
+        }
+    }
+}
+
+/// This function returns the buffered (multi-)polygon of the given geometry, shaped according to the given [BufferOptions].
+/// This function accepts every primitive which implements the [Buffer] trait, and is equivalent to `input_geometry.buffer_with(distance, options)`.
+/// 
+/// # Arguments
+/// 
+/// + `input_geometry`: the geometry to buffer.
+/// + `distance`: determines how distant from each edge of original geometry to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given geometry, and,
+///     - `-` to deflate (to add margins, make smaller) the given geometry.
+/// + `options`: determines the shape of the corners and ends of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{buffer_with_options, BufferOptions, JoinStyle, EndCapStyle, ArcResolution};
+/// use geo::{LineString, MultiPolygon};
+///
+/// let ls1 = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+/// let opts = BufferOptions::new()
+///     .join_style(JoinStyle::Round)
+///     .end_cap(EndCapStyle::Round)
+///     .arc_resolution(ArcResolution::MaxChordError(0.01));
+/// let p1: MultiPolygon = buffer_with_options(&ls1, 1., &opts);
+///
+/// assert_eq!(p1.0.len(), 1);
+/// ```
+pub fn buffer_with_options<T: BufferFloat, G: Buffer<T> + ?Sized>(input_geometry: &G, distance: T, options: &BufferOptions) -> MultiPolygon<T>{
+    input_geometry.buffer_with(distance, options)
+}
+
+/// This function is the fallible version of [buffer_with_options()]. It returns a [BufferError] instead of panicking
+/// if the given geometry cannot be buffered, e.g. it has a non-finite coordinate or a degenerate ring.
+/// 
+/// # Arguments
+/// 
+/// + `input_geometry`: the geometry to buffer.
+/// + `distance`: determines how distant from each edge of original geometry to each edge of the result polygon.
+/// + `options`: determines the shape of the corners and ends of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{try_buffer_with_options, BufferOptions, BufferError};
+/// use geo::LineString;
+///
+/// let ls1 = LineString::from(vec![(0., 0.), (f64::NAN, 0.)]);
+/// let res = try_buffer_with_options(&ls1, 1., &BufferOptions::new());
+///
+/// assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+/// ```
+pub fn try_buffer_with_options<T: BufferFloat, G: Buffer<T> + ?Sized>(input_geometry: &G, distance: T, options: &BufferOptions) -> Result<MultiPolygon<T>, BufferError>{
+    input_geometry.try_buffer_with(distance, options)
+}
 
 /// This function returns the buffered (multi-)polygon of the given polygon. This function creates a miter-joint-like corners around each convex vertex.
 /// 
@@ -169,8 +477,34 @@ use skeleton::Skeleton;
 /// assert_eq!(&expected_exterior, p2.0[0].exterior())
 ///
 /// ```
-pub fn buffer_polygon(input_polygon: &Polygon, distance: f64) -> MultiPolygon{
-    buffer_multi_polygon(&MultiPolygon::new(vec![input_polygon.clone()]), distance)
+pub fn buffer_polygon<T: BufferFloat>(input_polygon: &Polygon<T>, distance: T) -> MultiPolygon<T>{
+    input_polygon.buffer(distance)
+}
+
+/// This function is the fallible version of [buffer_polygon()]. It returns a [BufferError] instead of panicking
+/// if the given polygon cannot be buffered.
+/// 
+/// # Arguments
+/// 
+/// + `input_polygon`: `Polygon` to buffer.
+/// + `distance`: determine how distant from each edge of original polygon to each edge of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{try_buffer_polygon, BufferError};
+/// use geo::{Polygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]), vec![],
+/// );
+/// assert!(try_buffer_polygon(&p1, -0.2).is_ok());
+///
+/// let p2 = Polygon::new(LineString::from(vec![(0., 0.), (1., f64::NAN), (1., 1.)]), vec![]);
+/// assert!(matches!(try_buffer_polygon(&p2, -0.2), Err(BufferError::InvalidInput(_))));
+/// ```
+pub fn try_buffer_polygon<T: BufferFloat>(input_polygon: &Polygon<T>, distance: T) -> Result<MultiPolygon<T>, BufferError>{
+    input_polygon.try_buffer(distance)
 }
 
 /// This function returns the buffered (multi-)polygon of the given polygon, but creates a rounded corners around each convex vertex.
@@ -187,7 +521,7 @@ pub fn buffer_polygon(input_polygon: &Polygon, distance: f64) -> MultiPolygon{
 /// # Example
 /// 
 /// ```
-/// use geo_buffer::buffer_polygon;
+/// use geo_buffer::buffer_polygon_rounded;
 /// use geo::{Polygon, MultiPolygon, LineString};
 ///
 /// let p1 = Polygon::new(
@@ -201,8 +535,8 @@ pub fn buffer_polygon(input_polygon: &Polygon, distance: f64) -> MultiPolygon{
 /// <img src="https://raw.githubusercontent.com/1011-git/geo-buffer/main/assets/ex5.svg" style="padding: 25px 30%;"/>
 /// </details>
 /// 
-pub fn buffer_polygon_rounded(input_polygon: &Polygon, distance: f64) -> MultiPolygon{
-    buffer_multi_polygon_rounded(&MultiPolygon::new(vec![input_polygon.clone()]), distance)
+pub fn buffer_polygon_rounded<T: BufferFloat>(input_polygon: &Polygon<T>, distance: T) -> MultiPolygon<T>{
+    input_polygon.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
 }
 
 /// This function returns the buffered (multi-)polygon of the given multi-polygon. This function creates a miter-joint-like corners around each convex vertex.
@@ -232,12 +566,33 @@ pub fn buffer_polygon_rounded(input_polygon: &Polygon, distance: f64) -> MultiPo
 /// assert_eq!(&expected_exterior, mp2.0[0].exterior())
 ///
 /// ```
-pub fn buffer_multi_polygon(input_multi_polygon: &MultiPolygon, distance: f64) -> MultiPolygon{
-    let orientation = if distance < 0. {true} else {false};
-    let offset_distance = f64::abs(distance);
-    let skel = Skeleton::skeleton_of_polygon_vector(&input_multi_polygon.0, orientation);
-    let vq = skel.get_vertex_queue(offset_distance);
-    skel.apply_vertex_queue(&vq, offset_distance)
+pub fn buffer_multi_polygon<T: BufferFloat>(input_multi_polygon: &MultiPolygon<T>, distance: T) -> MultiPolygon<T>{
+    input_multi_polygon.buffer(distance)
+}
+
+/// This function is the fallible version of [buffer_multi_polygon()]. It returns a [BufferError] instead of panicking
+/// if the given multi-polygon cannot be buffered.
+/// 
+/// # Arguments
+/// 
+/// + `input_multi_polygon`: `MultiPolygon` to buffer.
+/// + `distance`: determine how distant from each edge of original polygon to each edge of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::try_buffer_multi_polygon;
+/// use geo::{Polygon, MultiPolygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]), vec![],
+/// );
+/// let mp1 = MultiPolygon::new(vec![p1]);
+/// let mp2 = try_buffer_multi_polygon(&mp1, 1.).unwrap();
+/// assert_eq!(mp2.0.len(), 1);
+/// ```
+pub fn try_buffer_multi_polygon<T: BufferFloat>(input_multi_polygon: &MultiPolygon<T>, distance: T) -> Result<MultiPolygon<T>, BufferError>{
+    input_multi_polygon.try_buffer(distance)
 }
 
 /// This function returns the buffered (multi-)polygon of the given multi-polygon, but creates a rounded corners around each convex vertex.
@@ -255,7 +610,7 @@ pub fn buffer_multi_polygon(input_multi_polygon: &MultiPolygon, distance: f64) -
 /// # Example
 /// 
 /// ```
-/// use geo_buffer::buffer_polygon;
+/// use geo_buffer::buffer_multi_polygon_rounded;
 /// use geo::{Polygon, MultiPolygon, LineString};
 ///
 /// let p1 = Polygon::new(
@@ -265,7 +620,7 @@ pub fn buffer_multi_polygon(input_multi_polygon: &MultiPolygon, distance: f64) -
 ///     LineString::from(vec![(3., 3.), (5., 3.), (5., 5.), (3., 5.)]), vec![],
 /// );
 /// let mp1 = MultiPolygon::new(vec![p1, p2]);
-/// let mp2 = buffer_multi_polygon(&mp1, 1.);
+/// let mp2 = buffer_multi_polygon_rounded(&mp1, 1.);
 /// ```
 /// 
 /// <details>
@@ -273,19 +628,377 @@ pub fn buffer_multi_polygon(input_multi_polygon: &MultiPolygon, distance: f64) -
 /// <img src="https://raw.githubusercontent.com/1011-git/geo-buffer/main/assets/ex6.svg" style="padding: 25px 30%;"/>
 /// </details>
 /// 
-pub fn buffer_multi_polygon_rounded(input_multi_polygon: &MultiPolygon, distance: f64) -> MultiPolygon{
-    let orientation = if distance < 0. {true} else {false};
-    let offset_distance = f64::abs(distance);
-    let skel = Skeleton::skeleton_of_polygon_vector(&input_multi_polygon.0, orientation);
-    let vq = skel.get_vertex_queue(offset_distance);
-    skel.apply_vertex_queue_rounded(&vq, offset_distance)
+pub fn buffer_multi_polygon_rounded<T: BufferFloat>(input_multi_polygon: &MultiPolygon<T>, distance: T) -> MultiPolygon<T>{
+    input_multi_polygon.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
+}
+
+/// This function returns the buffered (multi-)polygon of the given line string. This function creates a miter-joint-like corners around each
+/// convex vertex, and the ends of the line string are shaped by the given [EndCapStyle].
+/// 
+/// Since a line string has no interior, the result is empty if `distance` is not positive.
+/// 
+/// Unlike (multi-)polygons, line strings are not buffered via a straight skeleton. The outline of a line string is a polygon of zero
+/// area whose skeleton is the line string itself, thus the result is assembled as the union of the buffered segments, the joints
+/// around the inner vertices and the end caps instead.
+/// 
+/// # Arguments
+/// 
+/// + `input_line_string`: `LineString` to buffer.
+/// + `distance`: determines how distant from the line string to each edge of the result polygon.
+/// + `end_cap`: determines the shape of both ends of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{buffer_line_string, EndCapStyle};
+/// use geo::{MultiPolygon, LineString};
+///
+/// let ls1 = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+/// let p1: MultiPolygon = buffer_line_string(&ls1, 1., EndCapStyle::Flat);
+///
+/// assert_eq!(p1.0.len(), 1);
+/// assert_eq!(p1.0[0].interiors().len(), 0);
+/// ```
+pub fn buffer_line_string<T: BufferFloat>(input_line_string: &LineString<T>, distance: T, end_cap: EndCapStyle) -> MultiPolygon<T>{
+    input_line_string.buffer_with(distance, &BufferOptions::new().end_cap(end_cap))
+}
+
+/// This function returns the buffered (multi-)polygon of the given multi-line string. This function creates a miter-joint-like corners around each
+/// convex vertex, and the ends of each line string are shaped by the given [EndCapStyle]. The buffers of the constituent line strings
+/// are integrated if they overlap each other.
+/// 
+/// Since a line string has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_multi_line_string`: `MultiLineString` to buffer.
+/// + `distance`: determines how distant from the line strings to each edge of the result polygon.
+/// + `end_cap`: determines the shape of both ends of each line string.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{buffer_multi_line_string, EndCapStyle};
+/// use geo::{MultiLineString, LineString};
+///
+/// let ls1 = LineString::from(vec![(0., 0.), (4., 0.)]);
+/// let ls2 = LineString::from(vec![(2., -2.), (2., 2.)]);
+/// let mls1 = MultiLineString::new(vec![ls1, ls2]);
+/// let p1 = buffer_multi_line_string(&mls1, 0.5, EndCapStyle::Round);
+///
+/// assert_eq!(p1.0.len(), 1);
+/// ```
+pub fn buffer_multi_line_string<T: BufferFloat>(input_multi_line_string: &MultiLineString<T>, distance: T, end_cap: EndCapStyle) -> MultiPolygon<T>{
+    input_multi_line_string.buffer_with(distance, &BufferOptions::new().end_cap(end_cap))
+}
+
+/// This function returns the buffered (multi-)polygon of the given line segment. The ends of the line segment are shaped by the given [EndCapStyle].
+/// 
+/// Since a line segment has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_line`: `Line` to buffer.
+/// + `distance`: determines how distant from the line segment to each edge of the result polygon.
+/// + `end_cap`: determines the shape of both ends of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{buffer_line, EndCapStyle};
+/// use geo::{Line, MultiPolygon, Area};
+///
+/// let l1 = Line::new((0., 0.), (2., 0.));
+/// let p1: MultiPolygon = buffer_line(&l1, 1., EndCapStyle::Square);
+///
+/// assert_eq!(p1.unsigned_area(), 8.);
+/// ```
+pub fn buffer_line<T: BufferFloat>(input_line: &Line<T>, distance: T, end_cap: EndCapStyle) -> MultiPolygon<T>{
+    input_line.buffer_with(distance, &BufferOptions::new().end_cap(end_cap))
 }
 
-// pub fn skeleton_of_polygon(input_polygon: &Polygon, orientation: bool) -> Skeleton{
+/// This function returns the buffered polygon of the given point. This function creates an axis-aligned square centered at the given point,
+/// which is the limit of miter-joint-like corners.
+/// 
+/// Since a point has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_point`: `Point` to buffer.
+/// + `distance`: determines how distant from the point to each edge of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_point;
+/// use geo::{Point, MultiPolygon, LineString};
+///
+/// let p1 = Point::new(1., 1.);
+/// let p2: MultiPolygon = buffer_point(&p1, 1.);
+///
+/// let expected_exterior = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+/// assert_eq!(&expected_exterior, p2.0[0].exterior());
+/// ```
+pub fn buffer_point<T: BufferFloat>(input_point: &Point<T>, distance: T) -> MultiPolygon<T>{
+    input_point.buffer(distance)
+}
+
+/// This function returns the buffered polygon of the given point, which is an (approximated) disk centered at the given point.
+/// 
+/// Since a point has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_point`: `Point` to buffer.
+/// + `distance`: the radius of the result disk.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_point_rounded;
+/// use geo::{Point, MultiPolygon};
+///
+/// let p1 = Point::new(1., 1.);
+/// let p2: MultiPolygon = buffer_point_rounded(&p1, 1.);
+/// ```
+pub fn buffer_point_rounded<T: BufferFloat>(input_point: &Point<T>, distance: T) -> MultiPolygon<T>{
+    input_point.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
+}
+
+/// This function returns the buffered (multi-)polygon of the given multi-point. This function creates an axis-aligned square centered at each point,
+/// and the squares are integrated if they overlap each other.
+/// 
+/// Since a point has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_multi_point`: `MultiPoint` to buffer.
+/// + `distance`: determines how distant from each point to each edge of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_multi_point;
+/// use geo::{MultiPoint, MultiPolygon};
+///
+/// let mp1 = MultiPoint::from(vec![(0., 0.), (1., 0.), (5., 5.)]);
+/// let mp2: MultiPolygon = buffer_multi_point(&mp1, 1.);
+///
+/// assert_eq!(mp2.0.len(), 2);
+/// ```
+pub fn buffer_multi_point<T: BufferFloat>(input_multi_point: &MultiPoint<T>, distance: T) -> MultiPolygon<T>{
+    input_multi_point.buffer(distance)
+}
+
+/// This function returns the buffered (multi-)polygon of the given multi-point. This function creates an (approximated) disk centered at each point,
+/// and the disks are integrated if they overlap each other.
+/// 
+/// Since a point has no interior, the result is empty if `distance` is not positive.
+/// 
+/// # Arguments
+/// 
+/// + `input_multi_point`: `MultiPoint` to buffer.
+/// + `distance`: the radius of each disk.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_multi_point_rounded;
+/// use geo::{MultiPoint, MultiPolygon};
+///
+/// let mp1 = MultiPoint::from(vec![(0., 0.), (1., 0.), (5., 5.)]);
+/// let mp2: MultiPolygon = buffer_multi_point_rounded(&mp1, 1.);
+///
+/// assert_eq!(mp2.0.len(), 2);
+/// ```
+pub fn buffer_multi_point_rounded<T: BufferFloat>(input_multi_point: &MultiPoint<T>, distance: T) -> MultiPolygon<T>{
+    input_multi_point.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
+}
+
+/// This function returns the buffered (multi-)polygon of the given rectangle. This function creates a miter-joint-like corners around each vertex.
+/// 
+/// # Arguments
+/// 
+/// + `input_rect`: `Rect` to buffer.
+/// + `distance`: determines how distant from each edge of original rectangle to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given rectangle, and,
+///     - `-` to deflate (to add margins, make smaller) the given rectangle.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_rect;
+/// use geo::{Rect, MultiPolygon, LineString};
+///
+/// let r1 = Rect::new((0., 0.), (2., 1.));
+/// let p1: MultiPolygon = buffer_rect(&r1, 1.);
+///
+/// assert_eq!(p1.0.len(), 1);
+/// ```
+pub fn buffer_rect<T: BufferFloat>(input_rect: &Rect<T>, distance: T) -> MultiPolygon<T>{
+    input_rect.buffer(distance)
+}
+
+/// This function returns the buffered (multi-)polygon of the given rectangle, but creates a rounded corners around each vertex.
+/// 
+/// # Arguments
+/// 
+/// + `input_rect`: `Rect` to buffer.
+/// + `distance`: determines how distant from each edge of original rectangle to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given rectangle, and,
+///     - `-` to deflate (to add margins, make smaller) the given rectangle.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_rect_rounded;
+/// use geo::{Rect, MultiPolygon};
+///
+/// let r1 = Rect::new((0., 0.), (2., 1.));
+/// let p1: MultiPolygon = buffer_rect_rounded(&r1, 1.);
+/// ```
+pub fn buffer_rect_rounded<T: BufferFloat>(input_rect: &Rect<T>, distance: T) -> MultiPolygon<T>{
+    input_rect.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
+}
+
+/// This function returns the buffered (multi-)polygon of the given triangle. This function creates a miter-joint-like corners around each vertex.
+/// The vertices of the triangle may be given in either order.
+/// 
+/// # Arguments
+/// 
+/// + `input_triangle`: `Triangle` to buffer.
+/// + `distance`: determines how distant from each edge of original triangle to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given triangle, and,
+///     - `-` to deflate (to add margins, make smaller) the given triangle.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_triangle;
+/// use geo::{Triangle, MultiPolygon};
+///
+/// let t1 = Triangle::new((0., 0.).into(), (0., 4.).into(), (4., 0.).into());
+/// let p1: MultiPolygon = buffer_triangle(&t1, -0.5);
+///
+/// assert_eq!(p1.0.len(), 1);
+/// ```
+pub fn buffer_triangle<T: BufferFloat>(input_triangle: &Triangle<T>, distance: T) -> MultiPolygon<T>{
+    input_triangle.buffer(distance)
+}
+
+/// This function returns the buffered (multi-)polygon of the given triangle, but creates a rounded corners around each vertex.
+/// The vertices of the triangle may be given in either order.
+/// 
+/// # Arguments
+/// 
+/// + `input_triangle`: `Triangle` to buffer.
+/// + `distance`: determines how distant from each edge of original triangle to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given triangle, and,
+///     - `-` to deflate (to add margins, make smaller) the given triangle.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_triangle_rounded;
+/// use geo::{Triangle, MultiPolygon};
+///
+/// let t1 = Triangle::new((0., 0.).into(), (4., 0.).into(), (0., 4.).into());
+/// let p1: MultiPolygon = buffer_triangle_rounded(&t1, 0.5);
+/// ```
+pub fn buffer_triangle_rounded<T: BufferFloat>(input_triangle: &Triangle<T>, distance: T) -> MultiPolygon<T>{
+    input_triangle.buffer_with(distance, &BufferOptions::new().join_style(JoinStyle::Round))
+}
+
+/// This function returns the buffered (multi-)polygon of the given geometry by dispatching to the proper function of each primitive.
+/// This function creates a miter-joint-like corners around each convex vertex, line strings get the default [EndCapStyle], and points
+/// become axis-aligned squares.
+/// 
+/// Note that the result is empty if `distance` is not positive and the given geometry has no interior (e.g. `Point`, `LineString`).
+/// 
+/// The winding order of the input rings does not matter. In the result, the exterior ring of each polygon is oriented counter-clockwise
+/// and the interior rings are oriented clockwise.
+/// 
+/// # Arguments
+/// 
+/// + `input_geometry`: `Geometry` to buffer.
+/// + `distance`: determines how distant from each edge of original geometry to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given geometry, and,
+///     - `-` to deflate (to add margins, make smaller) the given geometry.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_geometry;
+/// use geo::{Geometry, Point, MultiPolygon, LineString};
+///
+/// let g1 = Geometry::Point(Point::new(1., 1.));
+/// let p1: MultiPolygon = buffer_geometry(&g1, 1.);
+///
+/// let expected_exterior = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+/// assert_eq!(&expected_exterior, p1.0[0].exterior());
+/// ```
+pub fn buffer_geometry<T: BufferFloat>(input_geometry: &Geometry<T>, distance: T) -> MultiPolygon<T>{
+    input_geometry.buffer(distance)
+}
+
+/// This function is the fallible version of [buffer_geometry()]. It returns a [BufferError] instead of panicking
+/// if the given geometry cannot be buffered.
+/// 
+/// # Arguments
+/// 
+/// + `input_geometry`: `Geometry` to buffer.
+/// + `distance`: determine how distant from each edge of original geometry to each edge of the result polygon.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::{try_buffer_geometry, BufferError};
+/// use geo::{Geometry, Point};
+///
+/// let g1 = Geometry::Point(Point::new(0., 0.));
+/// assert!(try_buffer_geometry(&g1, 1.).is_ok());
+/// assert!(matches!(try_buffer_geometry(&g1, f64::INFINITY), Err(BufferError::InvalidInput(_))));
+/// ```
+pub fn try_buffer_geometry<T: BufferFloat>(input_geometry: &Geometry<T>, distance: T) -> Result<MultiPolygon<T>, BufferError>{
+    input_geometry.try_buffer(distance)
+}
+
+/// This function returns the buffered (multi-)polygon of the given geometry collection. The buffers of all members are merged into a single `MultiPolygon`.
+/// 
+/// All polygonal members (`Polygon`, `MultiPolygon`, `Rect` and `Triangle`, including the ones in nested collections) are buffered together
+/// in the same way as [buffer_multi_polygon], so overlapping members (or members sharing an edge) are merged into their union before buffering.
+/// The other members are buffered separately as in [buffer_geometry] and then merged.
+/// 
+/// # Arguments
+/// 
+/// + `input_geometry_collection`: `GeometryCollection` to buffer.
+/// + `distance`: determines how distant from each edge of original geometry to each edge of the result polygon. The sign will be:
+///     - `+` to inflate (to add paddings, make bigger) the given geometries, and,
+///     - `-` to deflate (to add margins, make smaller) the given geometries.
+/// 
+/// # Example
+/// 
+/// ```
+/// use geo_buffer::buffer_geometry_collection;
+/// use geo::{Geometry, GeometryCollection, Point, Polygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![],
+/// );
+/// let gc1 = GeometryCollection::new_from(vec![Geometry::Polygon(p1), Geometry::Point(Point::new(3.5, 1.))]);
+/// let mp1 = buffer_geometry_collection(&gc1, 1.);
+///
+/// assert_eq!(mp1.0.len(), 1);
+/// ```
+pub fn buffer_geometry_collection<T: BufferFloat>(input_geometry_collection: &GeometryCollection<T>, distance: T) -> MultiPolygon<T>{
+    input_geometry_collection.buffer(distance)
+}
+
+// pub fn skeleton_of_polygon<T: BufferFloat>(input_polygon: &Polygon<T>, orientation: bool) -> Skeleton{
 //     Skeleton::skeleton_of_polygon(input_polygon, orientation)
 // }
 
-// pub fn skeleton_of_multi_polygon(input_multi_polygon: &MultiPolygon, orientation: bool) -> Skeleton{
+// pub fn skeleton_of_multi_polygon<T: BufferFloat>(input_multi_polygon: &MultiPolygon<T>, orientation: bool) -> Skeleton{
 //     Skeleton::skeleton_of_polygon_vector(&input_multi_polygon.0, orientation)
 // }
 
@@ -306,7 +1019,7 @@ pub fn buffer_multi_polygon_rounded(input_multi_polygon: &MultiPolygon, distance
 /// # Example
 /// 
 /// ```
-/// use geo_buffer::buffer_polygon;
+/// use geo_buffer::skeleton_of_polygon_to_linestring;
 /// use geo::{Polygon, MultiPolygon, LineString};
 ///
 /// let p1 = Polygon::new(
@@ -320,8 +1033,8 @@ pub fn buffer_multi_polygon_rounded(input_multi_polygon: &MultiPolygon, distance
 /// <img src="https://raw.githubusercontent.com/1011-git/geo-buffer/main/assets/ex7.svg" style="padding: 25px 30%;"/>
 /// </details>
 /// 
-pub fn skeleton_of_polygon_to_linestring(input_polygon: &Polygon, orientation: bool) -> Vec<LineString>{
-    Skeleton::skeleton_of_polygon(input_polygon, orientation).to_linestring()
+pub fn skeleton_of_polygon_to_linestring<T: BufferFloat>(input_polygon: &Polygon<T>, orientation: bool) -> Vec<LineString<T>>{
+    skeleton_of_multi_polygon_to_linestring(&MultiPolygon::new(vec![input_polygon.clone()]), orientation)
 }
 
 /// This function returns a set of `LineSting` which represents an instantiated straight skeleton of the given multi-polygon.
@@ -341,7 +1054,7 @@ pub fn skeleton_of_polygon_to_linestring(input_polygon: &Polygon, orientation: b
 /// # Example
 /// 
 /// ```
-/// use geo_buffer::buffer_polygon;
+/// use geo_buffer::skeleton_of_multi_polygon_to_linestring;
 /// use geo::{Polygon, MultiPolygon, LineString};
 ///
 /// let p1 = Polygon::new(
@@ -359,8 +1072,12 @@ pub fn skeleton_of_polygon_to_linestring(input_polygon: &Polygon, orientation: b
 /// <img src="https://raw.githubusercontent.com/1011-git/geo-buffer/main/assets/ex8.svg" style="padding: 25px 30%;"/>
 /// </details>
 /// 
-pub fn skeleton_of_multi_polygon_to_linestring(input_multi_polygon: &MultiPolygon, orientation: bool) -> Vec<LineString>{
-    Skeleton::skeleton_of_polygon_vector(&input_multi_polygon.0, orientation).to_linestring()
+pub fn skeleton_of_multi_polygon_to_linestring<T: BufferFloat>(input_multi_polygon: &MultiPolygon<T>, orientation: bool) -> Vec<LineString<T>>{
+    normalized(input_multi_polygon, None, |input, normalization, eps| {
+        let skel = Skeleton::skeleton_of_polygon_vector(&input.0, orientation, eps)?;
+        let lsv = skel.to_linestring(float::<T>(5.)/normalization.scale())?;
+        Ok::<_, BufferError>(lsv.iter().map(|ls| normalization.revert(ls)).collect())
+    }).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[cfg(test)]
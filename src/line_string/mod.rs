@@ -0,0 +1,159 @@
+use geo::GeoFloat;
+use geo_types::{LineString, Polygon};
+use num_traits::Float;
+
+use crate::util::*;
+use crate::options::{BufferOptions, EndCapStyle, JoinStyle};
+use crate::point::{polygon_of, disk, square};
+
+fn left_normal<T: GeoFloat>(dir: Coordinate<T>) -> Coordinate<T>{
+    Coordinate(-dir.1, dir.0)
+}
+
+fn direction<T: GeoFloat>(src: Coordinate<T>, dst: Coordinate<T>) -> Coordinate<T>{
+    let mut ray = Ray::new(src, dst);
+    ray.normalize();
+    ray.angle
+}
+
+/// Returns the cap around the endpoint `point` where `dir` is the (normalized) outward direction of the line string at the endpoint.
+fn end_cap_piece<T: GeoFloat>(point: Coordinate<T>, dir: Coordinate<T>, distance: T, options: &BufferOptions) -> Option<Polygon<T>>{
+    let normal = left_normal(dir);
+    match options.end_cap{
+        EndCapStyle::Flat => None,
+        EndCapStyle::Square => Some(polygon_of(vec![
+            point - normal*distance,
+            point - normal*distance + dir*distance,
+            point + normal*distance + dir*distance,
+            point + normal*distance,
+        ])),
+        EndCapStyle::Round => {
+            let from = Ray{origin: point, angle: normal*-T::one()};
+            let to = Ray{origin: point, angle: normal};
+            Some(polygon_of(arc_points(from, &to, distance, options.arc_resolution.step_angle(to_f64(distance)), true)))
+        },
+    }
+}
+
+/// The side of a line string, with respect to its direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side{
+    Left,
+    Right,
+}
+
+/// The shape of the line string at a vertex.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Corner{
+    /// The line string goes straight on, thus there is no corner.
+    Straight,
+    /// The corner is convex on the given side, i.e. the offset segments leave a gap on that side.
+    Convex(Side),
+    /// The line string turns back on itself, thus both sides are convex.
+    Turnback,
+}
+
+/// Returns the shape of the line string at the vertex between `dir1` and `dir2`.
+fn corner<T: GeoFloat>(dir1: Coordinate<T>, dir2: Coordinate<T>, eps: f64) -> Corner{
+    let op = dir1.outer_product(&dir2);
+    if feq(op, T::zero(), eps) {
+        return if dir1.inner_product(&dir2) > T::zero() {Corner::Straight} else {Corner::Turnback};
+    }
+    if op > T::zero() {Corner::Convex(Side::Right)} else {Corner::Convex(Side::Left)}
+}
+
+/// Returns the joint which fills the gap on the given (convex) side of `point`, where `dir1` (resp. `dir2`) is the
+/// direction of the incoming (resp. outgoing) segment.
+fn join_piece<T: GeoFloat>(point: Coordinate<T>, dir1: Coordinate<T>, dir2: Coordinate<T>, side: Side, distance: T, options: &BufferOptions, eps: f64) -> Option<Polygon<T>>{
+    let (out1, out2) = match side{
+        Side::Left => (left_normal(dir1), left_normal(dir2)),
+        Side::Right => (left_normal(dir1)*-T::one(), left_normal(dir2)*-T::one()),
+    };
+    let denom = T::one() + out1.inner_product(&out2);
+    if options.join_style == JoinStyle::Bevel {
+        if fleq(denom, T::zero(), eps) {return None;}
+        return Some(polygon_of(vec![point, point + out1*distance, point + out2*distance]));
+    }
+    if options.join_style == JoinStyle::Round {
+        let from = Ray{origin: point, angle: out1};
+        let to = Ray{origin: point, angle: out2};
+        let mut crdv = vec![point];
+        crdv.extend(arc_points(from, &to, distance, options.arc_resolution.step_angle(to_f64(distance)), side == Side::Right));
+        return Some(polygon_of(crdv));
+    }
+    if fleq(denom, T::zero(), eps) {
+        // The line string turns back on itself, thus the miter is infinitely long. It is clipped by the miter limit,
+        // or at the buffer distance as a square end cap if there is no limit.
+        let clip = if options.miter_limit.is_finite() {float::<T>(options.miter_limit)*distance} else {distance};
+        return Some(polygon_of(vec![
+            point,
+            point + out1*distance,
+            point + out1*distance + dir1*clip,
+            point + out2*distance - dir2*clip,
+            point + out2*distance,
+        ]));
+    }
+    if miter_ratio(out1, out2, eps) > options.miter_limit {
+        let mut crdv = vec![point, point + out1*distance];
+        crdv.extend(limited_miter_points(point, out1, out2, distance, options.miter_limit));
+        crdv.push(point + out2*distance);
+        return Some(polygon_of(crdv));
+    }
+    let miter = point + (out1 + out2)*(distance/denom);
+    Some(polygon_of(vec![point, point + out1*distance, miter, point + out2*distance]))
+}
+
+/// Returns a set of (possibly overlapping) polygons whose union is the buffer of the given line string.
+pub(crate) fn line_string_pieces<T: GeoFloat>(input_line_string: &LineString<T>, distance: T, options: &BufferOptions, eps: f64) -> Vec<Polygon<T>>{
+    let mut ret = Vec::new();
+    let mut crdv: Vec<Coordinate<T>> = Vec::new();
+    for c in input_line_string.coords(){
+        let c: Coordinate<T> = (*c).into();
+        if crdv.last().is_some_and(|l| l.eq_within(&c, eps)) {continue;}
+        crdv.push(c);
+    }
+    // `side` is the buffered side if only one side is buffered, and `None` if both sides are buffered.
+    let side = if !options.single_sided {None} else if distance > T::zero() {Some(Side::Left)} else {Some(Side::Right)};
+    let distance = if options.single_sided {Float::abs(distance)} else {distance};
+    if crdv.is_empty() || fleq(distance, T::zero(), eps) {return ret;}
+    if crdv.len() == 1 {
+        if options.single_sided {return ret;}
+        match options.end_cap{
+            EndCapStyle::Flat => {},
+            EndCapStyle::Square => ret.push(square(crdv[0], distance)),
+            EndCapStyle::Round => ret.push(disk(crdv[0], distance, options.arc_resolution.step_angle(to_f64(distance)))),
+        }
+        return ret;
+    }
+    let dirv = crdv.windows(2).map(|w| direction(w[0], w[1])).collect::<Vec<_>>();
+    for (i, dir) in dirv.iter().enumerate(){
+        let normal = left_normal(*dir);
+        let zero = Coordinate(T::zero(), T::zero());
+        let (right, left) = match side{
+            None => (normal*-distance, normal*distance),
+            Some(Side::Left) => (zero, normal*distance),
+            Some(Side::Right) => (zero, normal*-distance),
+        };
+        ret.push(polygon_of(vec![
+            crdv[i] + right,
+            crdv[i+1] + right,
+            crdv[i+1] + left,
+            crdv[i] + left,
+        ]));
+    }
+    for i in 1..crdv.len()-1{
+        // At a turnback, the gap is the same on both sides and is filled from the buffered side.
+        let join_side = match (corner(dirv[i-1], dirv[i], eps), side){
+            (Corner::Straight, _) => continue,
+            (Corner::Convex(c), Some(s)) if c != s => continue,
+            (Corner::Convex(c), _) => c,
+            (Corner::Turnback, s) => s.unwrap_or(Side::Left),
+        };
+        if let Some(p) = join_piece(crdv[i], dirv[i-1], dirv[i], join_side, distance, options, eps) {ret.push(p);}
+    }
+    if !options.single_sided {
+        if let Some(p) = end_cap_piece(crdv[0], dirv[0]*-T::one(), distance, options) {ret.push(p);}
+        if let Some(p) = end_cap_piece(crdv[crdv.len()-1], dirv[dirv.len()-1], distance, options) {ret.push(p);}
+    }
+    ret
+}
@@ -0,0 +1,97 @@
+use geo::{BoundingRect, GeoFloat, MapCoords};
+use geo_types::{Coord, MultiPolygon, Rect};
+
+use crate::util::*;
+use crate::options::BufferOptions;
+use crate::report::BufferReport;
+use crate::error::BufferError;
+
+/// This structure represents the similarity transformation which moves the input geometry to a local origin and scales it into
+/// a well-conditioned range, so that the geometry is buffered in the same way regardless of the scale and the location of the input.
+///
+/// The transformed bounding box lies in the square [-1.5, 1.5]². The scale is a power of two and the origin is a multiple of it,
+/// thus scaling (e.g. the buffer distance) is exact, and transforming the input coordinates is exact up to one rounding of the translation.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Normalization<T: GeoFloat>{
+    origin: Coordinate<T>,
+    scale: T,
+}
+
+impl<T: GeoFloat> Normalization<T>{
+    /// Returns the transformation for the given geometry. It is the identity if the geometry has no extent.
+    pub(crate) fn of<G>(geometry: &G) -> Self
+    where G: BoundingRect<T>, G::Output: Into<Option<Rect<T>>>{
+        let identity = Self{origin: Coordinate(T::zero(), T::zero()), scale: T::one()};
+        let Some(rect) = geometry.bounding_rect().into() else {
+            return identity;
+        };
+        let half_extent = T::max(rect.width(), rect.height()) / float(2.);
+        if !half_extent.is_normal() {return identity;}
+        let scale = T::powi(float(2.), to_f64(half_extent.log2().ceil()) as i32);
+        let center = rect.center();
+        let origin = Coordinate((center.x/scale).round()*scale, (center.y/scale).round()*scale);
+        Self{origin, scale}
+    }
+
+    /// Returns the scale factor, i.e. the length in the input which corresponds to the unit length in the transformed range.
+    pub(crate) fn scale(&self) -> T{
+        self.scale
+    }
+
+    /// Returns the transformed geometry of the given geometry.
+    pub(crate) fn apply<G: MapCoords<T, T, Output = G>>(&self, geometry: &G) -> G{
+        geometry.map_coords(|c| geo_types::coord!{x: (c.x - self.origin.0)/self.scale, y: (c.y - self.origin.1)/self.scale})
+    }
+
+    /// Returns the geometry transformed back from the transformed range to the original one.
+    pub(crate) fn revert<G: MapCoords<T, T, Output = G>>(&self, geometry: &G) -> G{
+        geometry.map_coords(|c| self.revert_coord(c))
+    }
+
+    /// Returns the coordinate transformed back from the transformed range to the original one. (See [Normalization::revert()].)
+    pub(crate) fn revert_coord(&self, c: Coord<T>) -> Coord<T>{
+        geo_types::coord!{x: c.x*self.scale + self.origin.0, y: c.y*self.scale + self.origin.1}
+    }
+
+    /// Returns the given tolerance (in the unit of the input) in the transformed range, or the default tolerance if it is `None`.
+    pub(crate) fn tolerance(&self, tolerance: Option<f64>) -> f64{
+        tolerance.map_or(default_tolerance::<T>(), |t| t/to_f64(self.scale))
+    }
+}
+
+/// Runs `f` with the given geometry transformed into the well-conditioned range, the transformation, and the given tolerance
+/// (in the unit of the input) in the transformed range, which the comparisons in `f` should use. Every buffer and straight skeleton
+/// goes through this function. (See [Normalization::tolerance()].)
+pub(crate) fn normalized<T, G, R, F>(geometry: &G, tolerance: Option<f64>, f: F) -> R
+where
+    T: GeoFloat,
+    G: BoundingRect<T> + MapCoords<T, T, Output = G>,
+    <G as BoundingRect<T>>::Output: Into<Option<Rect<T>>>,
+    F: FnOnce(&G, &Normalization<T>, f64) -> R,
+{
+    let normalization = Normalization::of(geometry);
+    f(&normalization.apply(geometry), &normalization, normalization.tolerance(tolerance))
+}
+
+/// Buffers the given geometry in the transformed range. (See [normalized()].)
+///
+/// `buffer` is called with the transformed geometry, the buffer distance, the options and the tolerance in the transformed range.
+/// The result and the distances in its report are transformed back to the original range.
+pub(crate) fn buffer_normalized<T, G, F>(geometry: &G, distance: T, options: &BufferOptions, buffer: F) -> Result<(MultiPolygon<T>, BufferReport), BufferError>
+where
+    T: GeoFloat,
+    G: BoundingRect<T> + MapCoords<T, T, Output = G>,
+    <G as BoundingRect<T>>::Output: Into<Option<Rect<T>>>,
+    F: FnOnce(&G, T, &BufferOptions, f64) -> Result<(MultiPolygon<T>, BufferReport), BufferError>,
+{
+    normalized(geometry, options.tolerance, |input, normalization, eps| {
+        let scale = normalization.scale();
+        let options = BufferOptions{
+            arc_resolution: options.arc_resolution.scaled(1./to_f64(scale)),
+            tolerance: Some(eps),
+            ..*options
+        };
+        let (res, report) = buffer(input, distance/scale, &options, eps)?;
+        Ok((normalization.revert(&res), report.scaled(to_f64(scale))))
+    })
+}
@@ -0,0 +1,162 @@
+/// This enumeration represents a style of both ends of a buffered (multi-)line string.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EndCapStyle{
+    /// The result ends exactly at each endpoint with a segment perpendicular to the line string.
+    Flat,
+    /// The result is extended beyond each endpoint by the buffer distance and ends with a segment perpendicular to the line string.
+    Square,
+    /// The result ends with a half-disk around each endpoint.
+    #[default]
+    Round,
+}
+
+/// This enumeration represents a style of corners around each convex vertex of the buffered geometry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JoinStyle{
+    /// Creates miter-joint-like corners, that is, the offset edges are extended until they meet each other.
+    #[default]
+    Miter,
+    /// Creates rounded corners, that is, each corner is replaced by a circular arc around the original vertex.
+    Round,
+    /// Creates beveled (chamfered) corners, that is, each corner is replaced by a single segment which joins the endpoints of the offset edges.
+    /// Points are buffered to squares as in [JoinStyle::Miter].
+    Bevel,
+}
+
+/// This enumeration represents how finely circular arcs (rounded corners, round end caps and disks around points) are approximated
+/// by line segments.
+///
+/// Each arc is divided into segments of equal angle, so that it is symmetric and starts and ends exactly at the tangent points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArcResolution{
+    /// Approximates a quarter circle by the given number of segments regardless of the radius.
+    QuadrantSegments(usize),
+    /// Approximates an arc by segments whose maximum distance from the exact arc (i.e. the chord error) does not exceed the given value.
+    /// Thus the number of segments grows with the radius.
+    MaxChordError(f64),
+}
+
+impl Default for ArcResolution{
+    fn default() -> Self{
+        ArcResolution::QuadrantSegments(16)
+    }
+}
+
+impl ArcResolution{
+    /// Returns the angle (in radian) swept by a single segment of an arc of the given radius.
+    pub(crate) fn step_angle(&self, radius: f64) -> f64{
+        let quadrant = std::f64::consts::FRAC_PI_2;
+        let step = match *self{
+            ArcResolution::QuadrantSegments(n) => quadrant/(n.max(1) as f64),
+            ArcResolution::MaxChordError(e) => {
+                if e <= 0. || radius <= e {quadrant}
+                else {2.*f64::acos(1. - e/radius)}
+            },
+        };
+        step.clamp(quadrant/1024., quadrant)
+    }
+
+    /// Returns the arc resolution for the geometry scaled by the given factor, i.e. the maximum chord error is scaled as well.
+    pub(crate) fn scaled(&self, factor: f64) -> Self{
+        match *self{
+            ArcResolution::QuadrantSegments(n) => ArcResolution::QuadrantSegments(n),
+            ArcResolution::MaxChordError(e) => ArcResolution::MaxChordError(e*factor),
+        }
+    }
+}
+
+/// This structure gathers the parameters which determine the shape of the buffered geometry.
+///
+/// The default value creates miter-joint-like corners and round end caps on both sides, which is the same as what `buffer_polygon()`,
+/// `buffer_multi_polygon()`, etc. do. Use it with [buffer_with_options][crate::buffer_with_options] or [Buffer::buffer_with][crate::Buffer::buffer_with].
+///
+/// # Example
+///
+/// ```
+/// use geo_buffer::{BufferOptions, JoinStyle, EndCapStyle};
+///
+/// let opts = BufferOptions::new().join_style(JoinStyle::Round).end_cap(EndCapStyle::Flat).single_sided(true);
+/// assert_eq!(opts.join_style, JoinStyle::Round);
+/// assert_eq!(opts.end_cap, EndCapStyle::Flat);
+/// assert!(opts.single_sided);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BufferOptions{
+    /// The style of corners around each convex vertex.
+    pub join_style: JoinStyle,
+    /// The style of both ends of each line string.
+    pub end_cap: EndCapStyle,
+    /// The maximum ratio of the length of a miter (i.e. the distance from the original vertex to the tip of the corner) to the buffer distance.
+    /// A miter-joint-like corner longer than this is clipped by a bevel perpendicular to the bisector at the distance `miter_limit * distance`
+    /// from the original vertex. Only affects [JoinStyle::Miter]. The default value is `f64::INFINITY`, i.e. corners are never clipped.
+    /// The miter of a line string turning back on itself is infinitely long, thus it is clipped at `miter_limit * distance`, or at
+    /// `distance` as a square end cap if `miter_limit` is infinite.
+    pub miter_limit: f64,
+    /// How finely circular arcs are approximated.
+    pub arc_resolution: ArcResolution,
+    /// If `true`, line strings are buffered only on one side: the left side for positive distances and the right side for negative distances.
+    /// The end caps are always flat in this case. This option does not affect the other primitives, since the sign of the distance already
+    /// determines the side for them.
+    pub single_sided: bool,
+    /// The distance (in the unit of the input coordinates) below which two points, or two times of events, are considered the same.
+    /// If `None`, which is the default value, the tolerance is relative to the extent of the input: the input is translated and scaled into
+    /// a well-conditioned range before it is buffered, and `1e-9` is used in that range. For `f32` coordinates, `1e-9` is scaled up to
+    /// about `2.3e-5`.
+    pub tolerance: Option<f64>,
+}
+
+impl Default for BufferOptions{
+    fn default() -> Self{
+        Self{
+            join_style: JoinStyle::default(),
+            end_cap: EndCapStyle::default(),
+            miter_limit: f64::INFINITY,
+            arc_resolution: ArcResolution::default(),
+            single_sided: false,
+            tolerance: None,
+        }
+    }
+}
+
+impl BufferOptions{
+    /// Creates and returns a [BufferOptions] with the default values.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Returns `self` with the given join style.
+    pub fn join_style(mut self, join_style: JoinStyle) -> Self{
+        self.join_style = join_style;
+        self
+    }
+
+    /// Returns `self` with the given end cap style.
+    pub fn end_cap(mut self, end_cap: EndCapStyle) -> Self{
+        self.end_cap = end_cap;
+        self
+    }
+
+    /// Returns `self` with the given miter limit.
+    pub fn miter_limit(mut self, miter_limit: f64) -> Self{
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Returns `self` with the given arc resolution.
+    pub fn arc_resolution(mut self, arc_resolution: ArcResolution) -> Self{
+        self.arc_resolution = arc_resolution;
+        self
+    }
+
+    /// Returns `self` with the given single-sidedness.
+    pub fn single_sided(mut self, single_sided: bool) -> Self{
+        self.single_sided = single_sided;
+        self
+    }
+
+    /// Returns `self` with the given tolerance.
+    pub fn tolerance(mut self, tolerance: f64) -> Self{
+        self.tolerance = Some(tolerance);
+        self
+    }
+}
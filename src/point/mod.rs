@@ -0,0 +1,39 @@
+use geo::GeoFloat;
+use geo_types::Polygon;
+
+use crate::util::*;
+use crate::options::{BufferOptions, JoinStyle};
+
+pub(crate) fn polygon_of<T: GeoFloat>(crdv: Vec<Coordinate<T>>) -> Polygon<T>{
+    Polygon::new(crdv.into_iter().map(|c| c.get_val()).collect::<Vec<_>>().into(), vec![])
+}
+
+/// Returns the (approximated) disk of radius `radius` around `center`.
+pub(crate) fn disk<T: GeoFloat>(center: Coordinate<T>, radius: T, step: f64) -> Polygon<T>{
+    let east = Ray{origin: center, angle: (T::one(), T::zero()).into()};
+    let west = Ray{origin: center, angle: (-T::one(), T::zero()).into()};
+    let mut crdv = arc_points(east, &west, radius, step, true);
+    crdv.pop();
+    crdv.extend(arc_points(west, &east, radius, step, true));
+    crdv.pop();
+    polygon_of(crdv)
+}
+
+/// Returns the axis-aligned square of half-width `radius` around `center`.
+pub(crate) fn square<T: GeoFloat>(center: Coordinate<T>, radius: T) -> Polygon<T>{
+    polygon_of(vec![
+        center + Coordinate(-radius, -radius),
+        center + Coordinate(radius, -radius),
+        center + Coordinate(radius, radius),
+        center + Coordinate(-radius, radius),
+    ])
+}
+
+/// Returns the buffer of the given point, or `None` if the buffer is empty.
+pub(crate) fn point_piece<T: GeoFloat>(center: Coordinate<T>, distance: T, options: &BufferOptions, eps: f64) -> Option<Polygon<T>>{
+    if fleq(distance, T::zero(), eps) {return None;}
+    match options.join_style{
+        JoinStyle::Miter | JoinStyle::Bevel => Some(square(center, distance)),
+        JoinStyle::Round => Some(disk(center, distance, options.arc_resolution.step_angle(to_f64(distance)))),
+    }
+}
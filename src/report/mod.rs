@@ -0,0 +1,39 @@
+/// This structure describes how a buffered (multi-)polygon was computed. It is returned together with the result by
+/// [Buffer::try_buffer_with_report()][crate::Buffer::try_buffer_with_report].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BufferReport{
+    /// The distance by which the vertices of the input were perturbed, or `None` if the result was computed from the input as it is.
+    ///
+    /// If the straight skeleton construction runs into an inconsistent state on the given input (typically because several events
+    /// almost coincide), it falls back to the input whose vertices are moved by a tiny distance in deterministic directions.
+    /// The result is then valid, but it may deviate from the exact buffer by about this distance.
+    pub perturbation: Option<f64>,
+    /// The largest distance by which a vertex where members or rings of the input touch each other was moved, or `None` if there
+    /// was no such vertex.
+    ///
+    /// The straight skeleton construction cannot handle coincident vertices, thus such vertices are pulled apart along their incoming
+    /// edges by a few times the tolerance (and a quarter of the edge at most) before building it. The result may deviate from the
+    /// exact buffer by about this distance around them.
+    pub separation: Option<f64>,
+}
+
+impl BufferReport{
+    /// Returns `true` if the result was computed from the perturbed input. (See [BufferReport::perturbation].)
+    pub fn fallback_used(&self) -> bool{
+        self.perturbation.is_some()
+    }
+
+    /// Returns the report of the result merged from the results of both reports, i.e. the largest distances are kept.
+    pub(crate) fn merge(self, other: Self) -> Self{
+        let max = |x: Option<f64>, y: Option<f64>| match (x, y){
+            (Some(x), Some(y)) => Some(f64::max(x, y)),
+            (x, y) => x.or(y),
+        };
+        Self{perturbation: max(self.perturbation, other.perturbation), separation: max(self.separation, other.separation)}
+    }
+
+    /// Returns the report whose distances are multiplied by the given scale.
+    pub(crate) fn scaled(self, scale: f64) -> Self{
+        Self{perturbation: self.perturbation.map(|x| x*scale), separation: self.separation.map(|x| x*scale)}
+    }
+}
@@ -0,0 +1,554 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use geo::TriangulateEarcut;
+use geo_types::{Coord, LineString, Polygon, MultiPolygon};
+
+use crate::error::BufferError;
+use crate::report::BufferReport;
+use crate::skeleton::{StraightSkeleton, InputIndex};
+use crate::util::*;
+use crate::normalization::normalized;
+
+/// This enumeration represents the pitch of the roof planes, i.e. the angle (in radian) between each roof plane and the horizontal plane.
+/// Each angle must lie in `[0, π/2)`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pitch{
+    /// Every roof plane has the given pitch.
+    Uniform(f64),
+    /// Each roof plane has the pitch of its footprint edge. The pitches are given in the order of the edges of the footprint, i.e. the edges
+    /// of the exterior ring followed by those of each interior ring, for each polygon in turn.
+    ///
+    /// Note that the straight skeleton is not weighted by the pitches: the roof planes are the faces of the straight skeleton lifted by
+    /// their own pitches. Thus two adjacent planes with different pitches would meet at different heights along their common ridge or hip.
+    /// Such planes may only meet at the points moved onto the wall of a gable, which are the intersections of the planes (see [Gable]),
+    /// e.g. the front and back planes of a saltbox roof. Otherwise the roof is rejected with [BufferError::InvalidInput].
+    PerEdge(Vec<f64>),
+}
+
+impl Default for Pitch{
+    fn default() -> Self{
+        Pitch::Uniform(std::f64::consts::FRAC_PI_6)
+    }
+}
+
+/// This enumeration represents the end of a roof over a footprint edge which is marked as a gable. (See [RoofOptions::gable()].)
+///
+/// The face of the edge is replaced by a vertical wall, and the points of the adjacent roof planes beyond the wall are moved onto it,
+/// i.e. to the intersections of the wall and the planes meeting there. Thus a ridge ending at the face extends to the wall.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gable{
+    /// The wall stands on the edge, i.e. the whole face is replaced.
+    Full,
+    /// The face remains a hip plane up to the given fraction (in `[0, 1)`) of its depth, and the wall stands on the top of the hip
+    /// plane, which makes a Dutch gable.
+    Dutch(f64),
+}
+
+/// This structure gathers the parameters which determine the shape of a roof. (See [RoofMesh].)
+///
+/// The default value creates a hip roof whose planes have the pitch of 30 degrees. Marking both short edges of a rectangle as
+/// [Gable::Full] makes a gable roof, and giving the long edges different pitches as well makes a saltbox roof.
+///
+/// # Example
+///
+/// ```
+/// use geo_buffer::{RoofOptions, Pitch};
+///
+/// let opts = RoofOptions::new().pitch(Pitch::Uniform(0.5));
+/// assert_eq!(opts.pitch, Pitch::Uniform(0.5));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoofOptions{
+    /// The pitch of the roof planes.
+    pub pitch: Pitch,
+    /// The gables by the index of their footprint edges, in the order of [Pitch::PerEdge]. The other edges end with hip planes.
+    pub gables: BTreeMap<usize, Gable>,
+}
+
+impl RoofOptions{
+    /// Creates and returns a [RoofOptions] with the default values.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Returns `self` with the given pitch.
+    pub fn pitch(mut self, pitch: Pitch) -> Self{
+        self.pitch = pitch;
+        self
+    }
+
+    /// Returns `self` where the footprint edge of the given index (in the order of [Pitch::PerEdge]) is marked as the given gable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geo_buffer::{RoofMesh, RoofOptions, Pitch, Gable};
+    /// use geo::{Polygon, LineString};
+    ///
+    /// let p1 = Polygon::new(
+    ///     LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![],
+    /// );
+    /// // A saltbox roof: the ridge is where the steep front plane meets the gentle back plane.
+    /// let pitches = vec![f64::atan(2.), 0., f64::atan(0.5), 0.];
+    /// let opts = RoofOptions::new().pitch(Pitch::PerEdge(pitches)).gable(1, Gable::Full).gable(3, Gable::Full);
+    /// let roof = RoofMesh::of_polygon(&p1, &opts).unwrap();
+    ///
+    /// let ridge: Vec<_> = roof.vertices().iter().filter(|v| v[2] > 0.).collect();
+    /// assert_eq!(ridge.len(), 2);
+    /// assert!(ridge.iter().all(|v| f64::abs(v[1] - 0.4) < 1e-12 && f64::abs(v[2] - 0.8) < 1e-12));
+    /// ```
+    pub fn gable(mut self, edge: usize, gable: Gable) -> Self{
+        self.gables.insert(edge, gable);
+        self
+    }
+}
+
+/// This structure represents a 3D mesh of the roof over a footprint, which is built from the straight skeleton of the footprint.
+///
+/// Each face of the mesh is the face of the straight skeleton swept by an edge of the footprint (see [StraightSkeleton::faces()]),
+/// lifted so that the height of each point is the time when the wavefront passes through it multiplied by the slope of the face.
+/// Thus the eaves are at the height `0` and the faces are oriented counter-clockwise seen from above. The faces of gables are
+/// post-processed into vertical walls (see [Gable]), which are oriented counter-clockwise seen from outside. The mesh is not closed,
+/// i.e. it contains neither the bottom face nor the walls under the eaves.
+///
+/// # Example
+///
+/// ```
+/// use geo_buffer::{RoofMesh, RoofOptions, Pitch};
+/// use geo::{Polygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![],
+/// );
+/// let opts = RoofOptions::new().pitch(Pitch::Uniform(std::f64::consts::FRAC_PI_4));
+/// let roof = RoofMesh::of_polygon(&p1, &opts).unwrap();
+///
+/// assert_eq!(roof.faces().len(), 4);
+/// assert_eq!(roof.vertices().len(), 6);
+/// assert!(roof.vertices().iter().all(|v| v[2] == 0. || f64::abs(v[2] - 1.) < 1e-12));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoofMesh<T: BufferFloat = f64>{
+    vertices: Vec<[T; 3]>,
+    faces: Vec<(InputIndex, Vec<usize>)>,
+    triangles: Vec<[usize; 3]>,
+    report: BufferReport,
+}
+
+impl<T: BufferFloat> RoofMesh<T>{
+    /// Builds the roof over the given footprint.
+    pub fn of_polygon(footprint: &Polygon<T>, options: &RoofOptions) -> Result<Self, BufferError>{
+        Self::of_polygon_vector(std::slice::from_ref(footprint), options)
+    }
+
+    /// Builds the roof over the given footprint consisting of several polygons. (See [RoofMesh::of_polygon()].)
+    pub fn of_multi_polygon(footprint: &MultiPolygon<T>, options: &RoofOptions) -> Result<Self, BufferError>{
+        Self::of_polygon_vector(&footprint.0, options)
+    }
+
+    /// Builds the roof over the given footprint in the well-conditioned range, and transforms it back. (See [normalized()].)
+    fn of_polygon_vector(footprint: &[Polygon<T>], options: &RoofOptions) -> Result<Self, BufferError>{
+        normalized(&MultiPolygon::new(footprint.to_vec()), None, |footprint, normalization, eps| {
+            let scale = normalization.scale();
+            let mut ret = Self::of_normalized_polygon_vector(&footprint.0, options, eps)?;
+            for v in &mut ret.vertices{
+                let c = normalization.revert_coord(Coord{x: v[0], y: v[1]});
+                *v = [c.x, c.y, v[2]*scale];
+            }
+            ret.report = ret.report.scaled(to_f64(scale));
+            Ok(ret)
+        })
+    }
+
+    fn of_normalized_polygon_vector(footprint: &[Polygon<T>], options: &RoofOptions, eps: f64) -> Result<Self, BufferError>{
+        let edges = footprint_edges(footprint);
+        let slopes = slopes(&options.pitch, edges.len())?;
+        let gables = gables(&options.gables, edges.len())?;
+        let skel = StraightSkeleton::of_normalized_polygon_vector(footprint, true, eps)?;
+        // The edges of the straight skeleton may be moved from the footprint edges by the perturbation and the separation.
+        let report = skel.report();
+        let tolerance = float::<T>(eps + report.perturbation.unwrap_or(0.) + report.separation.unwrap_or(0.));
+
+        let mut planes = Vec::new();
+        for (e, ring) in skel.face_rings()?{
+            let (s, t) = skeleton_edge(skel.input(), &e);
+            let edge = footprint_edge(&edges, (s, t), tolerance, |k| (slopes[k], gables[k]))?;
+            let slope = float::<T>(slopes[edge]);
+            let d = (t - s)/num_traits::Float::hypot(t.x - s.x, t.y - s.y);
+            let ring = &ring[..ring.len().saturating_sub(1)];
+            let points = ring.iter().map(|&(c, time, _)| [c.x, c.y, time*slope]).collect();
+            let nodes = ring.iter().map(|&(_, _, node)| node.ok_or_else(|| {
+                BufferError::InternalInconsistency(format!("the face of the footprint edge {e:?} is unbounded"))
+            })).collect::<Result<_, _>>()?;
+            planes.push(RoofPlane{edge, origin: s, normal: Coord{x: -d.y, y: d.x}, slope, points, nodes});
+        }
+        let mut ret = Self{vertices: Vec::new(), faces: Vec::new(), triangles: Vec::new(), report: skel.report()};
+        let mut vertex_index = HashMap::new();
+        for (i, ring, outward) in lift_gables(&planes, &gables, eps)?{
+            ret.push_face(edges[planes[i].edge].0, ring, outward, &mut vertex_index);
+        }
+        Ok(ret)
+    }
+
+    /// Appends the given face oriented counter-clockwise seen from the given direction, and its triangulation.
+    fn push_face(&mut self, edge: InputIndex, mut ring: Vec<[T; 3]>, outward: [T; 3], vertex_index: &mut HashMap<[u64; 3], usize>){
+        ring.dedup();
+        while ring.len() > 1 && ring.first() == ring.last() {ring.pop();}
+        if ring.len() < 3 {return;}
+        if dot(newell_normal(&ring), outward) < T::zero() {ring.reverse();}
+        // -0.0 and 0.0 are the same coordinate, thus they are mapped to the same key.
+        let key = |x: T| if x.is_zero() {0} else {to_f64(x).to_bits()};
+        let face = ring.iter().map(|&v| *vertex_index.entry(v.map(key)).or_insert_with(|| {
+            self.vertices.push(v);
+            self.vertices.len() - 1
+        })).collect::<Vec<_>>();
+
+        // Triangulates the projection of the face along the axis nearest to its direction, e.g. the walls of gables are not
+        // projected to the ground.
+        let axis = (0..3).max_by(|&a, &b| num_traits::Float::abs(outward[a]).partial_cmp(&num_traits::Float::abs(outward[b])).unwrap_or(std::cmp::Ordering::Equal)).unwrap_or(2);
+        let (x, y) = ((axis + 1)%3, (axis + 2)%3);
+        let polygon = Polygon::new(LineString::from(ring.iter().map(|v| Coord{x: v[x], y: v[y]}).collect::<Vec<_>>()), vec![]);
+        let raw = polygon.earcut_triangles_raw();
+        // The closing point of the ring is the same vertex as the first one.
+        let vertex = |i: usize| face[i % face.len()];
+        for k in raw.triangle_indices.chunks_exact(3){
+            let [a, b, c] = [vertex(k[0]), vertex(k[1]), vertex(k[2])];
+            let [p, q, r] = [a, b, c].map(|i| self.vertices[i]);
+            if dot(cross(sub(q, p), sub(r, p)), outward) < T::zero() {self.triangles.push([a, c, b]);}
+            else {self.triangles.push([a, b, c]);}
+        }
+        self.faces.push((edge, face));
+    }
+
+    /// Returns the vertices of the mesh as `[x, y, z]`, where `z` is the height above the eaves.
+    pub fn vertices(&self) -> &[[T; 3]]{
+        &self.vertices
+    }
+
+    /// Returns the faces of the mesh, i.e. the footprint edge of each roof plane and the ring of its vertices (as indices into
+    /// [RoofMesh::vertices()]) oriented counter-clockwise seen from outside. The faces may be non-convex. The edge of a [Gable::Dutch]
+    /// has two faces: the hip plane followed by the wall.
+    pub fn faces(&self) -> &[(InputIndex, Vec<usize>)]{
+        &self.faces
+    }
+
+    /// Returns the triangulation of [RoofMesh::faces()], where each triangle is oriented in the same way as its face.
+    pub fn triangles(&self) -> &[[usize; 3]]{
+        &self.triangles
+    }
+
+    /// Returns the [BufferReport] of the construction of the straight skeleton. (See [StraightSkeleton::report()].)
+    pub fn report(&self) -> BufferReport{
+        self.report
+    }
+
+    /// Returns the mesh in the Wavefront OBJ format, i.e. the vertices followed by the triangles.
+    pub fn to_obj(&self) -> Vec<u8>{
+        let mut ret = String::from("# roof mesh generated by geo-buffer\n");
+        for v in &self.vertices{
+            ret += &format!("v {} {} {}\n", to_f64(v[0]), to_f64(v[1]), to_f64(v[2]));
+        }
+        for t in &self.triangles{
+            ret += &format!("f {} {} {}\n", t[0] + 1, t[1] + 1, t[2] + 1);
+        }
+        ret.into_bytes()
+    }
+
+    /// Returns the triangles of the mesh in the binary STL format.
+    pub fn to_stl(&self) -> Vec<u8>{
+        let mut ret = Vec::with_capacity(84 + 50*self.triangles.len());
+        ret.extend_from_slice(b"roof mesh generated by geo-buffer");
+        ret.resize(80, b' ');
+        ret.extend_from_slice(&(self.triangles.len() as u32).to_le_bytes());
+        for t in &self.triangles{
+            let [a, b, c] = t.map(|i| self.vertices[i].map(to_f64));
+            let n = cross(sub(b, a), sub(c, a));
+            let len = f64::sqrt(dot(n, n));
+            let n = if len > 0. {n.map(|x| x/len)} else {n};
+            for x in [n, a, b, c].into_iter().flatten(){
+                ret.extend_from_slice(&(x as f32).to_le_bytes());
+            }
+            ret.extend_from_slice(&0u16.to_le_bytes());
+        }
+        ret
+    }
+}
+
+/// Returns the slope of each footprint edge, or an error if the pitch is invalid.
+fn slopes(pitch: &Pitch, edge_count: usize) -> Result<Vec<f64>, BufferError>{
+    let pitches = match pitch{
+        Pitch::Uniform(p) => vec![*p; edge_count],
+        Pitch::PerEdge(v) if v.len() == edge_count => v.clone(),
+        Pitch::PerEdge(v) => {
+            return Err(BufferError::InvalidInput(format!("{} pitches are given for {} edges", v.len(), edge_count)));
+        },
+    };
+    pitches.into_iter().map(|p| {
+        if (0. ..std::f64::consts::FRAC_PI_2).contains(&p) {Ok(f64::tan(p))}
+        else {Err(BufferError::InvalidInput(format!("the pitch {p:?} does not lie in [0, π/2)")))}
+    }).collect()
+}
+
+/// Returns the gable of each footprint edge, or an error if a gable is invalid.
+fn gables(gables: &BTreeMap<usize, Gable>, edge_count: usize) -> Result<Vec<Option<Gable>>, BufferError>{
+    let mut ret = vec![None; edge_count];
+    for (&edge, &gable) in gables{
+        if edge >= edge_count {
+            return Err(BufferError::InvalidInput(format!("the gable is given for the edge {edge} of {edge_count} edges")));
+        }
+        if let Gable::Dutch(x) = gable{
+            if !(0. ..1.).contains(&x) {
+                return Err(BufferError::InvalidInput(format!("the fraction {x:?} of the Dutch gable does not lie in [0, 1)")));
+            }
+        }
+        ret[edge] = Some(gable);
+    }
+    Ok(ret)
+}
+
+/// Returns each edge of the given polygons in the order of [Pitch::PerEdge].
+fn footprint_edges<T: BufferFloat>(footprint: &[Polygon<T>]) -> Vec<(InputIndex, Coord<T>, Coord<T>)>{
+    let mut ret = Vec::new();
+    for (polygon, p) in footprint.iter().enumerate(){
+        for (ring, ls) in std::iter::once(p.exterior()).chain(p.interiors()).enumerate(){
+            ret.extend(ls.lines().enumerate().map(|(index, l)| (InputIndex{polygon, ring, index}, l.start, l.end)));
+        }
+    }
+    ret
+}
+
+/// Returns the endpoints of the given edge of the polygons from which the straight skeleton is constructed.
+fn skeleton_edge<T: BufferFloat>(input: &[Polygon<T>], e: &InputIndex) -> (Coord<T>, Coord<T>){
+    let p = &input[e.polygon];
+    let ls = if e.ring == 0 {p.exterior()} else {&p.interiors()[e.ring-1]};
+    (ls.0[e.index], ls.0[e.index+1])
+}
+
+/// Returns the index of the first footprint edge which lies on the given edge of the straight skeleton.
+///
+/// Since the straight skeleton is constructed from the cleaned footprint, each of its edges consists of the footprint edges which
+/// were merged as collinear ones, or is a part of a footprint edge which was cut where overlapping polygons were merged, up to the
+/// given tolerance. The footprint edges lying on the same edge must share their roof, i.e. `roof` (e.g. the pitch and the gable)
+/// must be the same for them. Otherwise returns [BufferError::InvalidInput].
+fn footprint_edge<T: BufferFloat, R: PartialEq>(edges: &[(InputIndex, Coord<T>, Coord<T>)], (s, t): (Coord<T>, Coord<T>), tolerance: T, roof: impl Fn(usize) -> R) -> Result<usize, BufferError>{
+    let length = |a: Coord<T>, b: Coord<T>| num_traits::Float::hypot(b.x - a.x, b.y - a.y);
+    // Whether the segments are collinear and overlap by more than the tolerance, which is checked against the longer segment.
+    let overlapping = |(a, b): (Coord<T>, Coord<T>), (c, d): (Coord<T>, Coord<T>)| {
+        let ((a, b), (c, d)) = if length(a, b) > length(c, d) {((c, d), (a, b))} else {((a, b), (c, d))};
+        let len = length(c, d);
+        if len <= tolerance {return false;}
+        let u = (d - c)/len;
+        let along = |p: Coord<T>| u.x*(p.x - c.x) + u.y*(p.y - c.y);
+        let across = |p: Coord<T>| num_traits::Float::abs(u.x*(p.y - c.y) - u.y*(p.x - c.x));
+        if across(a) > tolerance || across(b) > tolerance {return false;}
+        let (lo, hi) = (num_traits::Float::min(along(a), along(b)), num_traits::Float::max(along(a), along(b)));
+        num_traits::Float::min(hi, len) - num_traits::Float::max(lo, T::zero()) > tolerance
+    };
+    let mut found = (0..edges.len()).filter(|&k| overlapping((edges[k].1, edges[k].2), (s, t)));
+    let Some(first) = found.next() else {
+        return Err(BufferError::InternalInconsistency(format!("the edge from {s:?} to {t:?} of the straight skeleton lies on no footprint edge")));
+    };
+    if let Some(k) = found.find(|&k| roof(k) != roof(first)){
+        return Err(BufferError::InvalidInput(format!(
+            "the footprint edges {:?} and {:?} lie on the same line and are merged, but their pitches or gables differ", edges[first].0, edges[k].0,
+        )));
+    }
+    Ok(first)
+}
+
+/// This structure represents a face of the straight skeleton lifted to the plane of its footprint edge.
+struct RoofPlane<T: BufferFloat>{
+    /// The index of the footprint edge.
+    edge: usize,
+    /// The start of the edge of the straight skeleton.
+    origin: Coord<T>,
+    /// The unit normal of the edge of the straight skeleton toward the inside of the footprint.
+    normal: Coord<T>,
+    slope: T,
+    /// The ring of the face, which is not closed.
+    points: Vec<[T; 3]>,
+    /// The node of the straight skeleton at each point of the ring. (See [StraightSkeleton::nodes()].)
+    nodes: Vec<usize>,
+}
+
+impl<T: BufferFloat> RoofPlane<T>{
+    /// Returns the distance from the line of the edge to the given point, which is positive inside the footprint.
+    fn distance(&self, p: [T; 3]) -> T{
+        self.normal.x*(p[0] - self.origin.x) + self.normal.y*(p[1] - self.origin.y)
+    }
+
+    /// Returns the coefficients `[a, b, c, d]` of the plane `ax + by + cz = d` of the face.
+    fn plane(&self) -> [T; 4]{
+        let (a, b) = (self.normal.x*self.slope, self.normal.y*self.slope);
+        [a, b, -T::one(), a*self.origin.x + b*self.origin.y]
+    }
+
+    /// Returns the coefficients of the vertical plane at the given distance from the line of the edge. (See [RoofPlane::plane()].)
+    fn wall(&self, distance: T) -> [T; 4]{
+        [self.normal.x, self.normal.y, T::zero(), self.normal.x*self.origin.x + self.normal.y*self.origin.y + distance]
+    }
+}
+
+/// A face of the mesh given by the index of its [RoofPlane], its ring and its outward direction.
+type MeshFace<T> = (usize, Vec<[T; 3]>, [T; 3]);
+
+/// Post-processes the faces of the roof for the given gables of the footprint edges, and returns the index of the plane, the ring and
+/// the outward direction of each face of the mesh.
+///
+/// The face of a gable edge is split by its wall into the hip part and the wall part (the former is empty for [Gable::Full]), and
+/// each point of the wall part is moved onto the wall where the planes across its arcs meet the wall. The points of the other faces
+/// are moved in the same way, and the points where their arcs cross the wall are inserted. The faces are matched by their common
+/// arcs, which consist of the same nodes of the straight skeleton.
+///
+/// Returns [BufferError::InvalidInput] if a node which is not moved onto a wall is lifted to different heights by the faces sharing it,
+/// i.e. adjacent roof planes with different pitches would leave a crack between them. (See [Pitch::PerEdge].)
+fn lift_gables<T: BufferFloat>(planes: &[RoofPlane<T>], gables: &[Option<Gable>], eps: f64) -> Result<Vec<MeshFace<T>>, BufferError>{
+    // The face across the arc from the first node to the second node, and the position of each node in its face.
+    let mut across = HashMap::new();
+    let mut position = HashMap::new();
+    for (i, f) in planes.iter().enumerate(){
+        let n = f.nodes.len();
+        for j in 0..n{
+            across.insert((f.nodes[(j+1)%n], f.nodes[j]), i);
+            position.insert((i, f.nodes[j]), j);
+        }
+    }
+    // The distance from the edge to the wall of each gable, and the tolerance to compare distances with it.
+    let walls = planes.iter().map(|f| gables[f.edge].map(|g| {
+        let depth = f.points.iter().fold(T::zero(), |acc, &p| num_traits::Float::max(acc, f.distance(p)));
+        let fraction = match g {Gable::Full => T::zero(), Gable::Dutch(x) => float(x)};
+        (depth*fraction, depth*float(1e-9))
+    })).collect::<Vec<_>>();
+    let wall = |g: usize| walls[g].unwrap_or((T::zero(), T::zero())).0;
+    // Whether the given point is beyond the wall of the gable face `g` (`Greater`), on it or before it.
+    let side = |g: usize, p: [T; 3]| {
+        let (d0, tolerance) = walls[g].unwrap_or((T::zero(), T::zero()));
+        let d = planes[g].distance(p) - d0;
+        if d > tolerance {Ordering::Greater} else if d < -tolerance {Ordering::Less} else {Ordering::Equal}
+    };
+    let gable_across = |a: usize, b: usize| across.get(&(a, b)).copied().filter(|&g| walls[g].is_some());
+    // The point on the wall which replaces the `j`-th point of the gable face `g`.
+    let on_wall = |g: usize, j: usize| {
+        let f = &planes[g];
+        let n = f.points.len();
+        let (prev, p, next) = (f.nodes[(j+n-1)%n], f.points[j], f.nodes[(j+1)%n]);
+        let neighbours = (across.get(&(prev, f.nodes[j])), across.get(&(f.nodes[j], next)));
+        if let (Some(&a), Some(&b)) = neighbours{
+            if walls[a].is_none() && walls[b].is_none() {
+                if let Some(x) = intersection([planes[a].plane(), planes[b].plane(), f.wall(wall(g))]) {return x;}
+            }
+        }
+        let d = f.distance(p) - wall(g);
+        [p[0] - f.normal.x*d, p[1] - f.normal.y*d, p[2]]
+    };
+    // The point where the arc from the `j`-th point to the `k`-th point of the gable face `g` crosses the wall.
+    let crossing = |g: usize, j: usize, k: usize| {
+        let f = &planes[g];
+        if let Some(&h) = across.get(&(f.nodes[j], f.nodes[k])){
+            if let Some(x) = intersection([planes[h].plane(), f.plane(), f.wall(wall(g))]) {return x;}
+        }
+        let (a, b) = (f.points[j], f.points[k]);
+        let r = (wall(g) - f.distance(a))/(f.distance(b) - f.distance(a));
+        [0, 1, 2].map(|i| a[i] + (b[i] - a[i])*r)
+    };
+
+    let up = [T::zero(), T::zero(), T::one()];
+    let mut ret = Vec::new();
+    // The face and the point of each node which is kept as it is.
+    let mut kept = Vec::new();
+    for (i, f) in planes.iter().enumerate(){
+        let n = f.points.len();
+        if walls[i].is_some(){
+            let (mut hip, mut wall) = (Vec::new(), Vec::new());
+            for j in 0..n{
+                let (a, b) = (f.points[j], f.points[(j+1)%n]);
+                let (sa, sb) = (side(i, a), side(i, b));
+                match sa{
+                    Ordering::Greater => wall.push(on_wall(i, j)),
+                    Ordering::Equal => {hip.push(a); wall.push(a); kept.push((i, j));},
+                    Ordering::Less => {hip.push(a); kept.push((i, j));},
+                }
+                if sa != Ordering::Equal && sb != Ordering::Equal && sa != sb{
+                    let c = crossing(i, j, (j+1)%n);
+                    hip.push(c);
+                    wall.push(c);
+                }
+            }
+            ret.push((i, hip, up));
+            ret.push((i, wall, [-f.normal.x, -f.normal.y, T::zero()]));
+            continue;
+        }
+        let mut ring = Vec::new();
+        for j in 0..n{
+            let (u, v, w) = (f.nodes[(j+n-1)%n], f.nodes[j], f.nodes[(j+1)%n]);
+            let p = f.points[j];
+            // The points of the gable face `g` at the given nodes, which are compared with its wall.
+            let at = |g: usize, node: usize| position.get(&(g, node)).copied();
+            let beyond = |g: usize, node: usize| at(g, node).is_some_and(|k| side(g, planes[g].points[k]) == Ordering::Greater);
+            let before_wall = |g: usize, node: usize| at(g, node).is_some_and(|k| side(g, planes[g].points[k]) == Ordering::Less);
+            let before = gable_across(u, v).filter(|&g| beyond(g, v));
+            let after = gable_across(v, w).filter(|&g| beyond(g, v));
+            let moved = |g: usize| at(g, v).map_or(p, |k| on_wall(g, k));
+            if let Some(g) = before{
+                if before_wall(g, u) {ring.push(crossing(g, position[&(g, v)], position[&(g, u)]));}
+                ring.push(moved(g));
+            }
+            if let Some(g) = after{
+                if before != Some(g) {ring.push(moved(g));}
+                if before_wall(g, w) {ring.push(crossing(g, position[&(g, w)], position[&(g, v)]));}
+            }
+            if before.is_none() && after.is_none() {ring.push(p); kept.push((i, j));}
+        }
+        ret.push((i, ring, up));
+    }
+
+    let mut height = HashMap::new();
+    for (i, j) in kept{
+        let (node, z) = (planes[i].nodes[j], planes[i].points[j][2]);
+        let &mut (k, h) = height.entry(node).or_insert((i, z));
+        if fneq(z, h, eps) {
+            return Err(BufferError::InvalidInput(format!(
+                "the roof planes of the footprint edges {} and {} meet at different heights since their pitches differ", planes[k].edge, planes[i].edge,
+            )));
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns the intersection of the given planes `ax + by + cz = d`, or `None` if they do not meet at a single point.
+fn intersection<T: BufferFloat>(planes: [[T; 4]; 3]) -> Option<[T; 3]>{
+    let det = |m: [[T; 3]; 3]| {
+        m[0][0]*(m[1][1]*m[2][2] - m[1][2]*m[2][1]) - m[0][1]*(m[1][0]*m[2][2] - m[1][2]*m[2][0]) + m[0][2]*(m[1][0]*m[2][1] - m[1][1]*m[2][0])
+    };
+    // Cramer's rule, where the `k`-th column is replaced by the constants.
+    let matrix = |k: usize| planes.map(|r| [0, 1, 2].map(|i| if i == k {r[3]} else {r[i]}));
+    let d = det(matrix(3));
+    let scale = planes.iter().fold(T::one(), |acc, r| acc*num_traits::Float::sqrt(r[0]*r[0] + r[1]*r[1] + r[2]*r[2]));
+    if num_traits::Float::abs(d) <= scale*float(1e-9) {return None;}
+    Some([0, 1, 2].map(|k| det(matrix(k))/d))
+}
+
+fn sub<T: BufferFloat>(a: [T; 3], b: [T; 3]) -> [T; 3]{
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot<T: BufferFloat>(a: [T; 3], b: [T; 3]) -> T{
+    a[0]*b[0] + a[1]*b[1] + a[2]*b[2]
+}
+
+fn cross<T: BufferFloat>(a: [T; 3], b: [T; 3]) -> [T; 3]{
+    [a[1]*b[2] - a[2]*b[1], a[2]*b[0] - a[0]*b[2], a[0]*b[1] - a[1]*b[0]]
+}
+
+/// Returns the normal of the given ring by Newell's method, whose length is twice the area of the ring.
+fn newell_normal<T: BufferFloat>(ring: &[[T; 3]]) -> [T; 3]{
+    let mut ret = [T::zero(); 3];
+    for (j, &a) in ring.iter().enumerate(){
+        let b = ring[(j+1)%ring.len()];
+        ret[0] = ret[0] + (a[1] - b[1])*(a[2] + b[2]);
+        ret[1] = ret[1] + (a[2] - b[2])*(a[0] + b[0]);
+        ret[2] = ret[2] + (a[0] - b[0])*(a[1] + b[1]);
+    }
+    ret
+}
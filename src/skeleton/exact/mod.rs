@@ -0,0 +1,471 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use geo::GeoFloat;
+use geo_types::{LineString, Polygon};
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::priority_queue::PriorityQueue;
+use crate::vertex_queue::*;
+use crate::util::*;
+use crate::error::BufferError;
+use super::{Chain, Event, Skeleton, VertexType, gather_chains, resolve_chains};
+
+type Q = BigRational;
+
+/// A point (or a vector) with exact rational coordinates. The derived order is the lexicographic one.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Point(Q, Q);
+
+impl Point{
+    fn from_coordinate<T: GeoFloat>(c: Coordinate<T>) -> Result<Self, BufferError>{
+        Ok(Point(rational(to_f64(c.0))?, rational(to_f64(c.1))?))
+    }
+
+    fn to_coordinate<T: GeoFloat>(&self) -> Coordinate<T>{
+        Coordinate(float(to_float(&self.0)), float(to_float(&self.1)))
+    }
+
+    fn dot(&self, other: &Point) -> Q{
+        &self.0*&other.0 + &self.1*&other.1
+    }
+
+    fn cross(&self, other: &Point) -> Q{
+        &self.0*&other.1 - &self.1*&other.0
+    }
+}
+
+impl Add for &Point{
+    type Output = Point;
+    fn add(self, rhs: &Point) -> Point{
+        Point(&self.0 + &rhs.0, &self.1 + &rhs.1)
+    }
+}
+
+impl Sub for &Point{
+    type Output = Point;
+    fn sub(self, rhs: &Point) -> Point{
+        Point(&self.0 - &rhs.0, &self.1 - &rhs.1)
+    }
+}
+
+impl Mul<&Q> for &Point{
+    type Output = Point;
+    fn mul(self, rhs: &Q) -> Point{
+        Point(&self.0*rhs, &self.1*rhs)
+    }
+}
+
+impl Neg for Point{
+    type Output = Point;
+    fn neg(self) -> Point{
+        Point(-self.0, -self.1)
+    }
+}
+
+fn rational(x: f64) -> Result<Q, BufferError>{
+    Q::from_float(x).ok_or_else(|| BufferError::NumericalFailure(format!("{x} has no exact rational value")))
+}
+
+fn to_float(q: &Q) -> f64{
+    q.to_f64().unwrap_or(f64::NAN)
+}
+
+fn to_time<T: GeoFloat>(q: &Q) -> T{
+    float(to_float(q))
+}
+
+/// Returns the square root of `q`, which is exact if `q` is the square of a rational number and the closest `f64` otherwise.
+fn sqrt(q: &Q) -> Result<Q, BufferError>{
+    let (n, d) = (q.numer().sqrt(), q.denom().sqrt());
+    if &(&n*&n) == q.numer() && &(&d*&d) == q.denom() {return Ok(Q::new(n, d));}
+    rational(to_float(q).sqrt())
+}
+
+/// Returns the unit vector with rational coordinates which approximates the direction of `d`.
+///
+/// The vector is given by the rational parametrization of the unit circle ((1-τ²)/(1+τ²), 2τ/(1+τ²)) at τ = tan(θ/2),
+/// thus it is exactly of unit length. It is exactly the direction of `d` if the length of `d` is rational, e.g. for the axis-parallel edges.
+fn unit_direction(d: &Point) -> Result<Point, BufferError>{
+    let flip = d.0.is_negative();
+    let d = if flip {-d.clone()} else {d.clone()};
+    let denom = sqrt(&d.dot(&d))? + &d.0;
+    if denom.is_zero() {return Err(BufferError::NumericalFailure("an edge of the input has zero length".to_string()));}
+    let tau = &d.1/denom;
+    let tau2 = &tau*&tau;
+    let one = Q::one();
+    let u = Point((&one - &tau2)/(&one + &tau2), (&tau + &tau)/(&one + &tau2));
+    Ok(if flip {-u} else {u})
+}
+
+/// An oriented edge of the input polygons, i.e. the line {p | n·p = c} where `u` is the unit direction and `n` is the unit normal
+/// toward the side into which the wavefront propagates. The wavefront edge at time t lies on the line {p | n·p = c + t}.
+struct Line{
+    u: Point,
+    n: Point,
+    c: Q,
+}
+
+/// Returns the point and the time at which the wavefront edges on the given three lines meet, or `None` if the lines have
+/// no unique common point, i.e. two of them are parallel.
+fn solve(lines: [&Line; 3]) -> Option<(Point, Q)>{
+    // Each line gives the equation n.x*x + n.y*y - t = c.
+    let det = |col: usize| -> Q{
+        let m = lines.map(|l| {
+            let mut row = [l.n.0.clone(), l.n.1.clone(), -Q::one()];
+            if col < 3 {row[col] = l.c.clone();}
+            row
+        });
+        &m[0][0]*(&m[1][1]*&m[2][2] - &m[1][2]*&m[2][1])
+        - &m[0][1]*(&m[1][0]*&m[2][2] - &m[1][2]*&m[2][0])
+        + &m[0][2]*(&m[1][0]*&m[2][1] - &m[1][1]*&m[2][0])
+    };
+    let d = det(3);
+    if d.is_zero() {return None;}
+    Some((Point(det(0)/&d, det(1)/&d), det(2)/&d))
+}
+
+/// Returns the velocity of a vertex between the wavefront edges on the given lines, which is normalized so that the vertex
+/// moves away from both lines by the unit distance in the unit time.
+fn velocity(left: &Line, right: &Line) -> Point{
+    let det = left.n.cross(&right.n);
+    if det.is_zero() {
+        // The lines are parallel. The vertex moves along the normal if they have the same direction, and the wavefront between
+        // two opposite lines collapses at this instant, thus the vertex stays.
+        if left.n == right.n {return left.n.clone();}
+        return Point(Q::zero(), Q::zero());
+    }
+    Point((&right.n.1 - &left.n.1)/&det, (&left.n.0 - &right.n.0)/&det)
+}
+
+/// A vertex of the wavefront which is at `origin` at `time` and lies between the edges on the lines `left` and `right`.
+struct Vertex{
+    left: usize,
+    right: usize,
+    origin: Point,
+    velocity: Point,
+    time: Q,
+    parent: usize,
+}
+
+impl Vertex{
+    fn new(left: usize, right: usize, origin: Point, time: Q, lines: &[Line]) -> Self{
+        let velocity = velocity(&lines[left], &lines[right]);
+        Vertex{left, right, origin, velocity, time, parent: usize::MAX}
+    }
+}
+
+/// The exact counterpart of [VertexType].
+enum Node{
+    Tree(Vertex),
+    Split{location: Point, split_into: Vec<usize>, time: Q},
+    Root{location: Point, time: Q},
+}
+
+impl Node{
+    fn vertex(&self) -> Result<&Vertex, BufferError>{
+        if let Node::Tree(v) = self {return Ok(v);}
+        Err(BufferError::InternalInconsistency("expected a tree vertex but a split or root vertex".to_string()))
+    }
+
+    fn set_parent(&mut self, nparent: usize) -> Result<(), BufferError>{
+        if let Node::Tree(v) = self {v.parent = nparent; return Ok(());}
+        Err(BufferError::InternalInconsistency("expected a tree vertex but a split or root vertex".to_string()))
+    }
+
+    fn to_vertex_type<T: GeoFloat>(&self, lines: &[Line]) -> VertexType<T>{
+        match self{
+            Node::Tree(v) => {
+                let origin = v.origin.to_coordinate();
+                VertexType::TreeVertex{
+                    axis: Ray{origin, angle: v.velocity.to_coordinate()},
+                    left_ray: Ray{origin, angle: (-lines[v.left].u.clone()).to_coordinate()},
+                    right_ray: Ray{origin, angle: lines[v.right].u.to_coordinate()},
+                    left_edge: v.left,
+                    right_edge: v.right,
+                    parent: v.parent,
+                    time_elapsed: to_time(&v.time),
+                }
+            },
+            Node::Split{location, split_into, time} => VertexType::new_split_vertex(location.to_coordinate(), split_into.clone(), to_time(time)),
+            Node::Root{location, time} => VertexType::new_root_vertex(location.to_coordinate(), to_time(time)),
+        }
+    }
+}
+
+/// The exact counterpart of the timeline of the floating-point construction. The events are ordered by the exact time, and the
+/// simultaneous events by their location, since every event resolves all the vertices and edges which meet there at once.
+#[derive(PartialEq)]
+enum Timeline{
+    ShrinkEvent{time: Q, location: Point, left_vertex: IndexType, right_vertex: IndexType, left_real: usize, right_real: usize,},
+    SplitEvent{time: Q, location: Point, anchor_vertex: IndexType, anchor_real: usize,},
+}
+
+impl Timeline{
+    fn key(&self) -> (&Q, &Point, u8, usize, usize){
+        match self{
+            Timeline::ShrinkEvent{time, location, left_real, right_real, ..} => (time, location, 0, *left_real, *right_real),
+            Timeline::SplitEvent{time, location, anchor_real, ..} => (time, location, 1, *anchor_real, *anchor_real),
+        }
+    }
+}
+
+impl PartialOrd for Timeline{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>{
+        Some(self.key().cmp(&other.key()))
+    }
+}
+
+fn make_shrink_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline>, node_vector: &[Node], lines: &[Line], is_init: bool) -> Result<(), BufferError>{
+    let mut lv = cv;
+    if vertex_queue.rv(cv)? == vertex_queue.lv(cv)? {return Ok(());}
+    for _ in 0..2{
+        let rv = vertex_queue.rv(lv)?;
+        let lv_real = vertex_queue.get_real_index(lv)?;
+        let rv_real = vertex_queue.get_real_index(rv)?;
+        let (l, r) = (node_vector[lv_real].vertex()?, node_vector[rv_real].vertex()?);
+        let event = solve([&lines[l.left], &lines[l.right], &lines[r.right]]).or_else(|| {
+            // Two of the lines are parallel, then the trajectories of the vertices are intersected directly.
+            let det = r.velocity.cross(&l.velocity);
+            if det.is_zero() {return None;}
+            let s = -((&r.origin - &l.origin).cross(&r.velocity)/&det);
+            Some((&l.origin + &(&l.velocity*&s), &l.time + &s))
+        });
+        if let Some((location, time)) = event{
+            if time >= l.time && time >= r.time{
+                event_pq.insert(Timeline::ShrinkEvent{time, location, left_vertex: lv, right_vertex: rv, left_real: lv_real, right_real: rv_real});
+            }
+        }
+        if is_init {break;}
+        lv = vertex_queue.lv(cv)?;
+    }
+    Ok(())
+}
+
+fn find_split_vertex(cv: IndexType, vertex_queue: &VertexQueue, node_vector: &[Node], lines: &[Line], orient: bool) -> Result<Vec<(Q, Point, IndexType, usize)>, BufferError>{
+    let mut ret = Vec::new();
+    let cv_real = vertex_queue.get_real_index(cv)?;
+    let c = node_vector[cv_real].vertex()?;
+    let reflex = (-lines[c.left].u.clone()).cross(&lines[c.right].u);
+    if orient && !reflex.is_positive() {return Ok(ret);}
+    if !orient && !reflex.is_negative() {return Ok(ret);}
+
+    for e in vertex_queue.iter(){
+        let (_, sv, sv_real) = e?;
+        let srv = vertex_queue.rv(sv)?;
+        if sv == cv || sv == vertex_queue.rv(cv)? || srv == cv || srv == vertex_queue.lv(cv)? {continue;}
+        let s = node_vector[sv_real].vertex()?;
+        let edge = &lines[s.right];
+        let (location, time) = match solve([&lines[c.left], &lines[c.right], edge]){
+            Some(x) => x,
+            None => {
+                // The edge is parallel to an edge of the vertex, then the trajectory of the vertex is intersected with the wavefront edge.
+                let denom = edge.n.dot(&c.velocity) - Q::one();
+                if denom.is_zero() {continue;}
+                let time = (&edge.c - edge.n.dot(&c.origin) + edge.n.dot(&c.velocity)*&c.time)/denom;
+                (&c.origin + &(&c.velocity*&(&time - &c.time)), time)
+            },
+        };
+        if time < c.time {continue;}
+        ret.push((time, location, sv, sv_real));
+    }
+    ret.sort_by(|a, b| (&a.0, &a.1, a.3).cmp(&(&b.0, &b.1, b.3)));
+    Ok(ret)
+}
+
+fn make_split_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline>, node_vector: &[Node], lines: &[Line], orient: bool) -> Result<(), BufferError>{
+    let resv = find_split_vertex(cv, vertex_queue, node_vector, lines, orient)?;
+    let cv_real = vertex_queue.get_real_index(cv)?;
+    for (time, location, _, _) in resv{
+        event_pq.insert(Timeline::SplitEvent{time, location, anchor_vertex: cv, anchor_real: cv_real});
+    }
+    Ok(())
+}
+
+/// Returns whether the given direction lies in the lower half plane, where the angles are measured from the positive x-axis.
+fn lower_half(d: &Point) -> bool{
+    d.1.is_negative() || (d.1.is_zero() && !d.0.is_positive())
+}
+
+/// The exact counterpart of [Skeleton::resolve_event()].
+fn resolve_event<T: GeoFloat>(time: &Q, location: &Point, vertex_queue: &VertexQueue, node_vector: &mut Vec<Node>, lines: &[Line], orient: bool) -> Result<Option<Event<T>>, BufferError>{
+    let position = |real: usize| -> Result<Point, BufferError>{
+        let v = node_vector[real].vertex()?;
+        Ok(&v.origin + &(&v.velocity*&(time - &v.time)))
+    };
+    let mut at = Vec::new();
+    for e in vertex_queue.iter(){
+        let (_, cv, cv_real) = e?;
+        if &position(cv_real)? == location {at.push(cv);}
+    }
+    if at.is_empty() {return Ok(None);}
+    let (mut chains, collapsed) = gather_chains(vertex_queue, &at)?;
+    let line = |(real, is_right): (usize, bool)| -> Result<usize, BufferError>{
+        let v = node_vector[real].vertex()?;
+        Ok(if is_right {v.right} else {v.left})
+    };
+    let mut is_convex = true;
+    for c in &chains{
+        let turn = (-lines[line(c.in_edge)?].u.clone()).cross(&lines[line(c.out_edge)?].u);
+        is_convex &= if orient {turn.is_negative()} else {turn.is_positive()};
+    }
+    if !is_convex{
+        // Only a reflex (or straight) vertex can reach a wavefront edge.
+        let mut member = vec![false; vertex_queue.content.len()];
+        for cv in &at {member[cv.get_index()?] = true;}
+        for e in vertex_queue.iter(){
+            let (_, sv, sv_real) = e?;
+            let srv = vertex_queue.rv(sv)?;
+            if member[sv.get_index()?] || member[srv.get_index()?] {continue;}
+            let l = &lines[node_vector[sv_real].vertex()?.right];
+            if l.n.dot(location) != &l.c + time {continue;}
+            let (sp, srp) = (position(sv_real)?, position(vertex_queue.get_real_index(srv)?)?);
+            if (location - &sp).dot(&l.u).is_positive() && (&srp - location).dot(&l.u).is_positive(){
+                chains.push(Chain{nodes: Vec::new(), left: sv, right: srv, in_edge: (sv_real, true), out_edge: (sv_real, true)});
+            }
+        }
+    }
+    let mut order = Vec::new();
+    for (i, c) in chains.iter().enumerate(){
+        for (d, is_out) in [(-lines[line(c.in_edge)?].u.clone(), false), (lines[line(c.out_edge)?].u.clone(), true)]{
+            let d = if orient {d} else {Point(d.0, -d.1)};
+            order.push((d, i, is_out));
+        }
+    }
+    let compare = |a: &Point, b: &Point| lower_half(a).cmp(&lower_half(b)).then_with(|| Q::zero().cmp(&a.cross(b)));
+    order.sort_by(|a, b| compare(&a.0, &b.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+    let mut groups: Vec<Vec<(usize, bool)>> = Vec::new();
+    for (i, (d, c, is_out)) in order.iter().enumerate(){
+        match groups.last_mut(){
+            Some(g) if compare(d, &order[i-1].0) == Ordering::Equal => g.push((*c, *is_out)),
+            _ => groups.push(vec![(*c, *is_out)]),
+        }
+    }
+    let (removed, created) = resolve_chains(&chains, &groups)?;
+    if removed.is_empty() && created.is_empty() && collapsed.is_empty() {return Ok(None);}
+    let edges = created.iter().map(|&(a, b)| Ok((line(chains[a].in_edge)?, line(chains[b].out_edge)?))).collect::<Result<Vec<_>, BufferError>>()?;
+    let mut links = Vec::new();
+    for ((a, b), (left, right)) in created.into_iter().zip(edges){
+        links.push((node_vector.len(), chains[a].left.get_index()?, chains[b].right.get_index()?));
+        node_vector.push(Node::Tree(Vertex::new(left, right, location.clone(), time.clone(), lines)));
+    }
+    let removed = removed.iter().chain(&collapsed).map(|cv| cv.get_index()).collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(Event{time: to_time(time), removed, created: links}))
+}
+
+/// The exact counterpart of [Skeleton::set_parents()].
+fn set_parents<T: GeoFloat>(event: &Event<T>, new_nodes: &[IndexType], location: &Point, time: &Q, vertex_queue: &VertexQueue, node_vector: &mut Vec<Node>) -> Result<(), BufferError>{
+    let hub = match event.created.len(){
+        0 => {
+            node_vector.push(Node::Root{location: location.clone(), time: time.clone()});
+            node_vector.len() - 1
+        },
+        1 => event.created[0].0,
+        _ => {
+            node_vector.push(Node::Split{location: location.clone(), split_into: event.created.iter().map(|x| x.0).collect(), time: time.clone()});
+            node_vector.len() - 1
+        },
+    };
+    for &cv in &event.removed{
+        node_vector[vertex_queue.content[cv].index.get_real_index()?].set_parent(hub)?;
+    }
+    for (cv, &(nv, ..)) in new_nodes.iter().zip(&event.created){
+        if !vertex_queue.content[cv.get_index()?].done {continue;}
+        let lv = vertex_queue.get_real_index(vertex_queue.lv(*cv)?)?;
+        node_vector[lv].set_parent(nv)?;
+        node_vector[nv] = Node::Root{location: location.clone(), time: time.clone()};
+    }
+    Ok(())
+}
+
+/// Returns the lines of the edges and the initial vertices of the given polygons, in the same order as the vertex queue.
+fn initialize_from_polygon_vector<T: GeoFloat>(input_polygon_vector: &[Polygon<T>], orient: bool) -> Result<(Vec<Line>, Vec<Node>), BufferError>{
+    let mut lines = Vec::new();
+    let mut nodes = Vec::new();
+    let rings = input_polygon_vector.iter().flat_map(|p| std::iter::once(p.exterior()).chain(p.interiors()));
+    for ring in rings.filter(|ls: &&LineString<T>| ls.0.len() > 1){
+        let crdv = ring.0.iter().map(|c| Point::from_coordinate((*c).into())).collect::<Result<Vec<_>, _>>()?;
+        let len = crdv.len() - 1;
+        let first = lines.len();
+        for w in crdv.windows(2){
+            let u = unit_direction(&(&w[1] - &w[0]))?;
+            let n = if orient {Point(-u.1.clone(), u.0.clone())} else {Point(u.1.clone(), -u.0.clone())};
+            let c = n.dot(&w[0]);
+            lines.push(Line{u, n, c});
+        }
+        for (cur, crd) in crdv[..len].iter().enumerate(){
+            let (left, right) = (first + (cur+len-1)%len, first + cur);
+            let (l, r) = (&lines[left], &lines[right]);
+            let det = l.n.cross(&r.n);
+            let origin = if !det.is_zero() {Point((&l.c*&r.n.1 - &r.c*&l.n.1)/&det, (&l.n.0*&r.c - &r.n.0*&l.c)/&det)}
+                else if l.n == r.n {crd.clone()} // A vertex between two edges of the same direction stays on the input point.
+                else {return Err(BufferError::NumericalFailure("two consecutive edges of the input are opposite".to_string()));};
+            nodes.push(Node::Tree(Vertex::new(left, right, origin, Q::zero(), &lines)));
+        }
+    }
+    Ok((lines, nodes))
+}
+
+/// Constructs the straight skeleton of the given (prepared) polygons with exact rational arithmetic.
+///
+/// Every input coordinate is converted to a rational number exactly, and the event times and locations are computed exactly
+/// from the lines of the input edges, thus the result depends on the input coordinates only. However, the direction of each edge
+/// is rounded to a rational unit vector unless its length is rational (see [unit_direction()]), thus only the events which coincide
+/// for the rounded lines (e.g. the collapse of a square) are detected as simultaneous ones. The events of a regular octagon, for example,
+/// may be resolved one by one at times which differ by about the rounding error.
+///
+/// The numerators and denominators grow with the depth of the events, and [find_split_vertex()] tests each reflex vertex against every
+/// wavefront edge whenever the vertex is created, thus the construction takes at least quadratic time in the number of vertices.
+/// The result is converted to the floating-point [Skeleton], so the buffers are assembled in the same way.
+pub(super) fn skeleton_of_prepared_polygons<T: BufferFloat>(input_polygon_vector: &Vec<Polygon<T>>, orient: bool, eps: f64) -> Result<Skeleton<T>, BufferError>{
+    let (lines, mut node_vector) = initialize_from_polygon_vector(input_polygon_vector, orient)?;
+    let mut event_pq = PriorityQueue::new();
+    let mut event_queue = Vec::new();
+    let mut vertex_queue = VertexQueue::new();
+    vertex_queue.initialize_from_polygon_vector(input_polygon_vector);
+    let initial_vertex_queue = vertex_queue.clone();
+    for e in vertex_queue.iter(){
+        let (_, cv, _) = e?;
+        make_shrink_event(cv, &vertex_queue, &mut event_pq, &node_vector, &lines, true)?;
+        make_split_event(cv, &vertex_queue, &mut event_pq, &node_vector, &lines, orient)?;
+    }
+
+    while let Some(x) = event_pq.pop(){
+        let (time, location) = match x{
+            Timeline::ShrinkEvent{time, location, left_vertex, right_vertex, left_real, right_real} => {
+                if vertex_queue.content[left_vertex.get_index()?].done || vertex_queue.content[right_vertex.get_index()?].done || vertex_queue.get_real_index(left_vertex)? != left_real || vertex_queue.get_real_index(right_vertex)? != right_real {
+                    continue;
+                }
+                (time, location)
+            },
+            Timeline::SplitEvent{time, location, anchor_vertex, anchor_real} => {
+                if vertex_queue.content[anchor_vertex.get_index()?].done || vertex_queue.get_real_index(anchor_vertex)? != anchor_real {
+                    continue;
+                }
+                (time, location)
+            },
+        };
+        let Some(new_event) = resolve_event(&time, &location, &vertex_queue, &mut node_vector, &lines, orient)? else {continue};
+        let new_nodes = Skeleton::apply_event(&mut vertex_queue, &new_event)?;
+        set_parents(&new_event, &new_nodes, &location, &time, &vertex_queue, &mut node_vector)?;
+        vertex_queue.cleanup()?;
+        for cv in new_nodes{
+            if vertex_queue.content[cv.get_index()?].done {continue;}
+            make_shrink_event(cv, &vertex_queue, &mut event_pq, &node_vector, &lines, false)?;
+            make_split_event(cv, &vertex_queue, &mut event_pq, &node_vector, &lines, orient)?;
+            if node_vector[vertex_queue.get_real_index(cv)?].vertex()?.velocity == Point(Q::zero(), Q::zero()){
+                // The edges of the new vertex are opposite and overlap, which collapse up to the nearer neighbor at this instant.
+                for nv in [vertex_queue.lv(cv)?, vertex_queue.rv(cv)?]{
+                    let nv_real = vertex_queue.get_real_index(nv)?;
+                    let v = node_vector[nv_real].vertex()?;
+                    let location = &v.origin + &(&v.velocity*&(&time - &v.time));
+                    event_pq.insert(Timeline::SplitEvent{time: time.clone(), location, anchor_vertex: nv, anchor_real: nv_real});
+                }
+            }
+        }
+        event_queue.push(new_event);
+    }
+    let ray_vector = node_vector.iter().map(|x| x.to_vertex_type(&lines)).collect();
+    Ok(Skeleton{input_polygon_vector: input_polygon_vector.clone(), ray_vector, event_queue, initial_vertex_queue, perturbation: None, separation: None, tolerance: eps})
+}
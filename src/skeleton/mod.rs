@@ -1,83 +1,105 @@
 use std::cmp::Ordering;
 use std::fmt;
 
-use geo::{Winding, Contains};
+use geo::{GeoFloat, Winding, Contains};
 use geo::winding_order::WindingOrder;
 use geo_types::{Polygon, MultiPolygon, LineString};
+use num_traits::Float;
 
 use crate::priority_queue::PriorityQueue;
 use crate::vertex_queue::*;
 use crate::util::*;
+use crate::options::ArcResolution;
+use crate::error::{BufferError, ensure_finite};
+use crate::cleaning::{prepare_polygons, perturb_polygons};
+use crate::report::BufferReport;
+
+#[cfg(feature = "exact")]
+mod exact;
+mod straight_skeleton;
+
+pub use straight_skeleton::{StraightSkeleton, SkeletonNode, SkeletonNodeKind, SkeletonArc, InputIndex};
 
 #[derive(Debug)]
-#[allow(dead_code)]
-pub(crate) enum VertexType{
-    TreeVertex{axis: Ray, left_ray: Ray, right_ray: Ray, parent: usize, time_elapsed: f64,},
-    SplitVertex{anchor: usize, location: Coordinate, split_left: usize, split_right: usize, time_elapsed: f64,},
-    RootVertex{location: Coordinate, time_elapsed: f64,}
+#[allow(dead_code, clippy::enum_variant_names)]
+pub(crate) enum VertexType<T: GeoFloat>{
+    TreeVertex{axis: Ray<T>, left_ray: Ray<T>, right_ray: Ray<T>, left_edge: usize, right_edge: usize, parent: usize, time_elapsed: T,},
+    SplitVertex{location: Coordinate<T>, split_into: Vec<usize>, time_elapsed: T,},
+    RootVertex{location: Coordinate<T>, time_elapsed: T,}
 }
 
-impl VertexType{
-    fn init_tree_vertex(lv: Coordinate, cv: Coordinate, rv: Coordinate, orient: bool) -> Self{
+impl<T: GeoFloat> VertexType<T>{
+    fn init_tree_vertex(lv: Coordinate<T>, cv: Coordinate<T>, rv: Coordinate<T>, (left_edge, right_edge): (usize, usize), orient: bool, eps: f64) -> Result<Self, BufferError>{
         let r1 = Ray::new(cv, lv);
         let r2 = Ray::new(cv, rv);
-        let mut r3 = r1.bisector(&r2, cv, orient);
-        r3.angle = r3.angle/(r3.point_by_ratio(1.).dist_ray(&r2));
-        VertexType::TreeVertex { axis: r3, left_ray: r1, right_ray: r2, parent: usize::MAX, time_elapsed: 0. }
+        let mut r3 = r1.bisector(&r2, cv, orient, eps);
+        r3.angle = r3.angle/(r3.point_by_ratio(T::one()).dist_ray(&r2));
+        ensure_finite(r3.angle.0 + r3.angle.1, "the bisector of an input vertex")?;
+        Ok(VertexType::TreeVertex { axis: r3, left_ray: r1, right_ray: r2, left_edge, right_edge, parent: usize::MAX, time_elapsed: T::zero() })
     }
 
-    fn new_tree_vertex(location: Coordinate, left_ray: Ray, right_ray: Ray, orient: bool) -> Self{
-        let mut axis = left_ray.bisector(&right_ray, location, orient);
-        axis.angle = axis.angle/f64::abs(axis.point_by_ratio(1.).dist_ray(&left_ray)-axis.point_by_ratio(0.).dist_ray(&left_ray));
+    fn new_tree_vertex(location: Coordinate<T>, left_ray: Ray<T>, right_ray: Ray<T>, (left_edge, right_edge): (usize, usize), orient: bool, eps: f64) -> Result<Self, BufferError>{
+        let mut axis = left_ray.bisector(&right_ray, location, orient, eps);
+        let (l, r) = (left_ray.angle/left_ray.angle.norm(), right_ray.angle/right_ray.angle.norm());
+        if feq(l.outer_product(&r), T::zero(), eps) && l.inner_product(&r) > T::zero(){
+            // The edges are opposite and lie on the same line, i.e. the wavefront between them collapses at this instant, thus the vertex stays.
+            axis.angle = Coordinate(T::zero(), T::zero());
+        }
+        else{
+            axis.angle = axis.angle/Float::abs(axis.point_by_ratio(T::one()).dist_ray(&left_ray)-axis.point_by_ratio(T::zero()).dist_ray(&left_ray));
+        }
+        ensure_finite(axis.angle.0 + axis.angle.1, "the bisector of a skeleton node")?;
         let time_elapsed = axis.origin.dist_ray(&left_ray);
-        VertexType::TreeVertex { axis, left_ray, right_ray, parent: usize::MAX, time_elapsed }
+        Ok(VertexType::TreeVertex { axis, left_ray, right_ray, left_edge, right_edge, parent: usize::MAX, time_elapsed })
     }
 
-    fn new_split_vertex(anchor: usize, location: Coordinate, split_left: usize, split_right: usize, time_elapsed: f64) -> Self{
-        VertexType::SplitVertex { anchor, location, split_left, split_right, time_elapsed }
+    fn new_split_vertex(location: Coordinate<T>, split_into: Vec<usize>, time_elapsed: T) -> Self{
+        VertexType::SplitVertex { location, split_into, time_elapsed }
     }
 
-    fn new_root_vertex(location: Coordinate, time_elapsed: f64) -> Self{
-        VertexType::RootVertex { location: location, time_elapsed: time_elapsed }
+    fn new_root_vertex(location: Coordinate<T>, time_elapsed: T) -> Self{
+        VertexType::RootVertex { location, time_elapsed }
     }
 
     #[allow(dead_code)]
-    fn initialize_from_polygon(input_polygon: &Polygon, orient: bool) -> Vec<Self>{
-        Self::initialize_from_polygon_vector(&vec![input_polygon.clone()], orient)
+    fn initialize_from_polygon(input_polygon: &Polygon<T>, orient: bool, eps: f64) -> Result<Vec<Self>, BufferError>{
+        Self::initialize_from_polygon_vector(&vec![input_polygon.clone()], orient, eps)
     }
 
-    fn initialize_from_polygon_vector(input_polygon_vector: &Vec<Polygon>, orient: bool) -> Vec<Self>{
+    fn initialize_from_polygon_vector(input_polygon_vector: &Vec<Polygon<T>>, orient: bool, eps: f64) -> Result<Vec<Self>, BufferError>{
         let mut ret = Vec::new();
         for p in input_polygon_vector{
+            let offset = ret.len();
             let len = p.exterior().0.len() - 1;
             for cur in 0..len{
                 let prv = (cur+len-1)%len;
                 let nxt = (cur+1)%len;
-                let new_vertex = VertexType::init_tree_vertex(p.exterior().0[prv].into(), p.exterior().0[cur].into(), p.exterior().0[nxt].into(), orient);
+                let new_vertex = VertexType::init_tree_vertex(p.exterior().0[prv].into(), p.exterior().0[cur].into(), p.exterior().0[nxt].into(), (prv+offset, cur+offset), orient, eps)?;
                 ret.push(new_vertex);
             }
             for i in 0..p.interiors().len(){
+                let offset = ret.len();
                 let len = p.interiors()[i].0.len()-1;
                 for cur in 0..len{
                     let prv = (cur+len-1)%len;
                     let nxt = (cur+1)%len;
-                    let new_node = VertexType::init_tree_vertex(p.interiors()[i].0[prv].into(), p.interiors()[i].0[cur].into(), p.interiors()[i].0[nxt].into(), orient);
+                    let new_node = VertexType::init_tree_vertex(p.interiors()[i].0[prv].into(), p.interiors()[i].0[cur].into(), p.interiors()[i].0[nxt].into(), (prv+offset, cur+offset), orient, eps)?;
                     ret.push(new_node);
                 }
             }
         }
-        ret
+        Ok(ret)
     }
 
-    fn unwrap_location(&self) -> Coordinate{
+    fn unwrap_location(&self) -> Coordinate<T>{
         match self{
-            VertexType::TreeVertex { axis, .. } => axis.origin.clone(),
-            VertexType::SplitVertex { location, .. } => location.clone(),
-            VertexType::RootVertex { location, .. } => location.clone(),
+            VertexType::TreeVertex { axis, .. } => axis.origin,
+            VertexType::SplitVertex { location, .. } => *location,
+            VertexType::RootVertex { location, .. } => *location,
         }
     }
 
-    fn unwrap_time(&self) -> f64{
+    fn unwrap_time(&self) -> T{
         match self{
             VertexType::TreeVertex { time_elapsed, .. } => *time_elapsed,
             VertexType::SplitVertex { time_elapsed, .. } => *time_elapsed,
@@ -85,115 +107,228 @@ impl VertexType{
         }
     }
 
-    fn unwrap_ray(&self) -> Ray{
+    fn unwrap_ray(&self) -> Result<Ray<T>, BufferError>{
         if let VertexType::TreeVertex { axis, .. } = self{
-            return axis.clone();
+            return Ok(*axis);
         }
-        panic!("Expected VertexType::TreeVertex");
+        Err(BufferError::InternalInconsistency(format!("expected a tree vertex but {:?}", self)))
     }
 
-    fn unwrap_base_ray(&self) -> (Ray, Ray){
+    fn unwrap_base_ray(&self) -> Result<(Ray<T>, Ray<T>), BufferError>{
         if let VertexType::TreeVertex { left_ray, right_ray, .. } = self{
-            return (left_ray.clone(), right_ray.clone());
+            return Ok((*left_ray, *right_ray));
         }
-        panic!("Expected VertexType::TreeVertex but {:?}", self);
+        Err(BufferError::InternalInconsistency(format!("expected a tree vertex but {:?}", self)))
     }
 
-    fn set_parent(&mut self, nparent: usize){
+    /// Returns the indices of the input edges on which the left and right edges of this vertex lie. The input edge of an index
+    /// starts at the input vertex of the same index.
+    fn unwrap_edges(&self) -> Result<(usize, usize), BufferError>{
+        if let VertexType::TreeVertex { left_edge, right_edge, .. } = self{
+            return Ok((*left_edge, *right_edge));
+        }
+        Err(BufferError::InternalInconsistency(format!("expected a tree vertex but {:?}", self)))
+    }
+
+    fn set_parent(&mut self, nparent: usize) -> Result<(), BufferError>{
         if let VertexType::TreeVertex { parent, .. } = self{
-          *parent = nparent;
+            *parent = nparent;
+            return Ok(());
         }
-        else {panic!("Expected VertexType::TreeVertex but {:?}", self)};
+        Err(BufferError::InternalInconsistency(format!("expected a tree vertex but {:?}", self)))
     }
 }
 
-#[derive(PartialEq)]
-enum Event{
-    VertexEvent{time: f64, merge_from: usize, merge_to: usize},
-    EdgeEvent{time: f64, split_from: usize, split_into: usize, split_to_left: usize, split_to_right: usize},
+/// The distances by which the input vertices are perturbed when the construction of the straight skeleton fails, in the multiples
+/// of the tolerance. (See [Skeleton::skeleton_of_polygon_vector()].)
+const PERTURBATIONS: [f64; 3] = [16., 256., 4096.];
+
+/// A candidate of a split event: its time, its location, and the vertex where the split edge starts with its real index.
+type SplitCandidate<T> = (T, Coordinate<T>, IndexType, usize);
+
+/// A change of the wavefront at an instant: the nodes `removed` of the vertex queue are removed, and a node is added for each
+/// (real index, left node, right node) in `created`. (See [VertexQueue::reconnect()].)
+struct Event<T: GeoFloat>{
+    time: T,
+    removed: Vec<usize>,
+    created: Vec<(usize, usize, usize)>,
 }
 
-impl PartialOrd for Event{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let x1 = match self{
-            Event::VertexEvent { time, merge_from, merge_to } => (*time, *merge_from, *merge_to, 0, 0),
-            Event::EdgeEvent { time, split_from, split_into, split_to_left, split_to_right } => (*time, *split_from, *split_into, *split_to_left, *split_to_right,),
-        };
-        let x2 = match other{
-            Event::VertexEvent { time, merge_from, merge_to } => (*time, *merge_from, *merge_to, 0, 0),
-            Event::EdgeEvent { time, split_from, split_into, split_to_left, split_to_right } => (*time, *split_from, *split_into, *split_to_left, *split_to_right,),
-        };
-        Some(x1.partial_cmp(&x2).unwrap())
+enum Timeline<T: GeoFloat>{
+    ShrinkEvent{time: T, location: Coordinate<T>, left_vertex: IndexType, right_vertex: IndexType, left_real: usize, right_real: usize,},
+    SplitEvent{time: T, location: Coordinate<T>, anchor_vertex: IndexType, anchor_real: usize,},
+}
+
+impl<T: GeoFloat> fmt::Display for Timeline<T>{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match self{
+            Timeline::ShrinkEvent { left_real, right_real, .. } => write!(f, "Shrink {} and {}", *left_real, *right_real),
+            Timeline::SplitEvent { anchor_real, ..} => write!(f, "Split {}", *anchor_real),
+        }
     }
 }
 
-impl Event{
-    fn unwrap_time(&self) -> f64{
+impl<T: GeoFloat> Timeline<T>{
+    fn key(&self) -> (T, Coordinate<T>, u8, usize, usize){
         match self{
-            Event::VertexEvent { time, ..} => *time,
-            Event::EdgeEvent { time, ..} => *time,
+            Timeline::ShrinkEvent { time, location, left_real, right_real, .. } => (*time, *location, 0, *left_real, *right_real),
+            Timeline::SplitEvent { time, location, anchor_real, .. } => (*time, *location, 1, *anchor_real, *anchor_real),
         }
     }
 }
 
-#[derive(PartialEq)]
-enum Timeline{
-    ShrinkEvent{time: f64, location: Coordinate, left_vertex: IndexType, right_vertex: IndexType, left_real: usize, right_real: usize, tie_break: f64,},
-    SplitEvent{time: f64, location: Coordinate, anchor_vertex: IndexType, anchor_real: usize,},
+/// The events are ordered by the total order of their times, and the simultaneous events by their locations, so that the order is
+/// transitive even for the times which differ by less than the tolerance. Such events are merged by [Skeleton::resolve_event()] instead.
+impl<T: GeoFloat> Ord for Timeline<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let total = |x: T, y: T| to_f64(x).total_cmp(&to_f64(y));
+        let (x1, x2) = (self.key(), other.key());
+        total(x1.0, x2.0)
+            .then_with(|| total(x1.1.0, x2.1.0))
+            .then_with(|| total(x1.1.1, x2.1.1))
+            .then_with(|| (x1.2, x1.3, x1.4).cmp(&(x2.2, x2.3, x2.4)))
+    }
 }
 
-impl fmt::Display for Timeline{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
-        match self{
-            Timeline::ShrinkEvent { left_real, right_real, .. } => write!(f, "Shrink {} and {}", *left_real, *right_real),
-            Timeline::SplitEvent { anchor_real, ..} => write!(f, "Split {}", *anchor_real),
+impl<T: GeoFloat> PartialOrd for Timeline<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: GeoFloat> PartialEq for Timeline<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: GeoFloat> Eq for Timeline<T> {}
+
+/// The nodes removed around an event and the pairs of the chains between which the new vertices are created.
+type Reconnection = (Vec<IndexType>, Vec<(usize, usize)>);
+
+/// A maximal run of consecutive wavefront vertices which meet at the location of an event, or a wavefront edge which passes
+/// through the location (then `nodes` is empty). The wavefront reaches the location along `in_edge` and leaves it along `out_edge`,
+/// where an edge is given by the real index of a vertex and whether it is the right edge of the vertex.
+struct Chain{
+    nodes: Vec<IndexType>,
+    left: IndexType,
+    right: IndexType,
+    in_edge: (usize, bool),
+    out_edge: (usize, bool),
+}
+
+/// Groups the given wavefront vertices, which meet at the location of an event, into the maximal runs of consecutive vertices.
+/// Returns the runs and the vertices of the cycles which lie at the location entirely.
+fn gather_chains(vertex_queue: &VertexQueue, at: &[IndexType]) -> Result<(Vec<Chain>, Vec<IndexType>), BufferError>{
+    let mut member = vec![false; vertex_queue.content.len()];
+    for cv in at{
+        member[cv.get_index()?] = true;
+    }
+    let mut seen = vec![false; vertex_queue.content.len()];
+    let mut chains = Vec::new();
+    let mut collapsed = Vec::new();
+    for &cv in at{
+        if seen[cv.get_index()?] {continue;}
+        let mut first = cv;
+        while member[vertex_queue.lv(first)?.get_index()?] && vertex_queue.lv(first)? != cv{
+            first = vertex_queue.lv(first)?;
+        }
+        let cycle = member[vertex_queue.lv(first)?.get_index()?];
+        let mut nodes = Vec::new();
+        let mut cur = if cycle {cv} else {first};
+        while member[cur.get_index()?] && !seen[cur.get_index()?]{
+            seen[cur.get_index()?] = true;
+            nodes.push(cur);
+            cur = vertex_queue.rv(cur)?;
         }
+        if cycle{
+            collapsed.extend(nodes);
+            continue;
+        }
+        let (Some(&first), Some(&last)) = (nodes.first(), nodes.last()) else {continue};
+        chains.push(Chain{
+            left: vertex_queue.lv(first)?, right: vertex_queue.rv(last)?,
+            in_edge: (vertex_queue.get_real_index(first)?, false), out_edge: (vertex_queue.get_real_index(last)?, true),
+            nodes,
+        });
     }
+    Ok((chains, collapsed))
 }
 
-impl PartialOrd for Timeline {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let t1 = match self{
-            Timeline::ShrinkEvent { time, .. } => *time,
-            Timeline::SplitEvent { time, ..} => *time,
-        };
-        let t2 = match other{
-            Timeline::ShrinkEvent { time, .. } => *time,
-            Timeline::SplitEvent { time, ..} => *time,
-        };
-        if fneq(t1, t2) {return Some(t1.partial_cmp(&t2).unwrap());}
-        let x1 = match self{
-            Timeline::ShrinkEvent { location, left_real, right_real, tie_break, .. }
-            => (1, tie_break, location, left_real, right_real),
-            Timeline::SplitEvent { location, anchor_real, .. }
-            => (0, &0., location, anchor_real, anchor_real),
-        };
-        let x2 = match other{
-            Timeline::ShrinkEvent { location, left_real, right_real, tie_break, .. }
-            => (1, tie_break, location, left_real, right_real),
-            Timeline::SplitEvent { location, anchor_real, .. }
-            => (0, &0., location, anchor_real, anchor_real),
-        };
-        Some(x1.partial_cmp(&x2).unwrap())
+/// Reconnects the wavefront around the location of an event. `groups` lists the directions in which the wavefront of each chain
+/// reaches (`false`) and leaves (`true`) the location, sorted by angle in the direction of the propagation and grouped by the
+/// same direction.
+///
+/// Each chain burns the sector from the direction it reaches to the direction it leaves, thus the directions should alternate.
+/// The unburned sector between the chain `b` leaving and the chain `a` reaching next becomes a new vertex between the edge
+/// which `a` reaches along and the edge which `b` leaves along. Returns the removed nodes and the pairs (a, b) of the new vertices.
+fn resolve_chains(chains: &[Chain], groups: &[Vec<(usize, bool)>]) -> Result<Reconnection, BufferError>{
+    let inconsistent = || BufferError::InternalInconsistency("the wavefront around an event intersects itself".to_string());
+    // The burned sectors only touch each other, thus a chain leaves before another chain reaches in the same direction. The directions
+    // in a group are arranged so that they alternate in this way, starting from the group before which the number of the chains
+    // leaving minus the number of the chains reaching is the least.
+    let mut balance = vec![0];
+    for g in groups{
+        balance.push(balance[balance.len()-1] + g.iter().map(|x| if x.1 {1} else {-1}).sum::<i32>());
     }
+    let start = (0..groups.len()).min_by_key(|&i| balance[i]).unwrap_or(0);
+    let mut order = Vec::new();
+    for k in 0..groups.len(){
+        let i = (start+k)%groups.len();
+        let (mut reach, mut leave): (Vec<_>, Vec<_>) = groups[i].iter().copied().partition(|x| !x.1);
+        let mut is_out = balance[i] == balance[start];
+        reach.reverse();
+        leave.reverse();
+        while let Some(x) = if is_out {leave.pop()} else {reach.pop()}{
+            order.push(x);
+            is_out = !is_out;
+        }
+        if !reach.is_empty() || !leave.is_empty() {return Err(inconsistent());}
+    }
+    let n = order.len();
+    if (0..n).any(|i| order[i].1 == order[(i+1)%n].1) {return Err(inconsistent());}
+    let mut involved = vec![false; chains.len()];
+    let mut created = Vec::new();
+    for i in (0..n).filter(|&i| order[i].1){
+        let (a, b) = (order[(i+1)%n].0, order[i].0);
+        // A single vertex or an edge between its own directions does not change.
+        if a == b && chains[a].nodes.len() <= 1 {continue;}
+        involved[a] = true;
+        involved[b] = true;
+        created.push((a, b));
+    }
+    let removed = chains.iter().zip(involved).filter(|(_, x)| *x).flat_map(|(c, _)| c.nodes.iter().copied()).collect();
+    Ok((removed, created))
 }
 
 /// This module implements a core logic of the polygon buffering algorithm. In the normal cases, you don't need to know how this 
 /// module works, nor need to use this module.
-pub(crate) struct Skeleton{
-    ray_vector: Vec<VertexType>,
-    event_queue: Vec<Event>,
+pub(crate) struct Skeleton<T: BufferFloat>{
+    input_polygon_vector: Vec<Polygon<T>>,
+    ray_vector: Vec<VertexType<T>>,
+    event_queue: Vec<Event<T>>,
     initial_vertex_queue: VertexQueue,
+    perturbation: Option<f64>,
+    separation: Option<f64>,
+    /// The tolerance of the comparisons in the construction and in the assembly of the buffers. (See [normalized()][crate::normalization::normalized].)
+    tolerance: f64,
 }
 
-impl Skeleton{
+impl<T: BufferFloat> Skeleton<T>{
 
-    pub(crate) fn apply_vertex_queue(&self, vertex_queue: &VertexQueue, offset_distance: f64) -> MultiPolygon{
-        let mut res = Vec::new();
+    /// Traverses the wavefront in the given vertex queue at the given offset distance and assembles the result polygons.
+    /// `corner` pushes the points of the corner around the vertex of the given (real) index, where the remaining time of the vertex
+    /// and the orientation of the skeleton are given.
+    fn collect_wavefront<F>(&self, vertex_queue: &VertexQueue, offset_distance: T, mut corner: F) -> Result<MultiPolygon<T>, BufferError>
+    where F: FnMut(&mut Vec<Coordinate<T>>, usize, T, bool) -> Result<(), BufferError>{
+        if vertex_queue.start_vertex.is_empty() {return Ok(MultiPolygon::new(vec![]));}
+        let orient = self.get_orientation()?;
         let mut lsv = Vec::new();
         let mut crdv= Vec::new();
         let mut cur_vidx = usize::MAX;
-        for (vidx, _, idx) in vertex_queue.iter(){
+        for e in vertex_queue.iter(){
+            let (vidx, _, idx) = e?;
             if vidx != cur_vidx{
                 if cur_vidx < usize::MAX {
                     let mut ls = LineString::from(crdv);
@@ -203,8 +338,8 @@ impl Skeleton{
                 cur_vidx = vidx;
                 crdv = Vec::new();
             }
-            let crd = self.ray_vector[idx].unwrap_ray().point_by_ratio(offset_distance-self.ray_vector[idx].unwrap_time());
-            crdv.push(crd);
+            let time_left = offset_distance-self.ray_vector[idx].unwrap_time();
+            corner(&mut crdv, idx, time_left, orient)?;
         }
         if cur_vidx < usize::MAX {
             let mut ls = LineString::from(crdv);
@@ -212,322 +347,428 @@ impl Skeleton{
             lsv.push(ls);
         }
         for ls in &lsv{
-            if ls.winding_order() == Some(WindingOrder::CounterClockwise){
-                let p1: Polygon = Polygon::new(
-                    ls.clone(), vec![],
-                );
-                res.push(p1);
+            if ls.coords().any(|c| !c.x.is_finite() || !c.y.is_finite()) {
+                return Err(BufferError::NumericalFailure("the result contains a non-finite coordinate".to_string()));
             }
         }
+        let shells: Vec<Polygon<T>> = lsv.iter()
+            .filter(|ls| ls.winding_order() == Some(WindingOrder::CounterClockwise))
+            .map(|ls| Polygon::new(ls.clone(), vec![]))
+            .collect();
+        // The containment depth of each exterior ring, i.e. the number of the other exterior rings which contain it.
+        let depth = (0..shells.len())
+            .map(|i| (0..shells.len()).filter(|&j| j != i && shells[j].contains(shells[i].exterior())).count())
+            .collect::<Vec<_>>();
+        let mut res = shells.clone();
         for ls in &lsv{
             if ls.winding_order() == Some(WindingOrder::Clockwise){
-                for e in &mut res{
-                    if e.contains(ls){
-                        e.interiors_push(ls.clone());
-                        break;
-                    }
-                }
+                // A hole belongs to the innermost exterior ring which contains it.
+                let parent = (0..shells.len())
+                    .filter(|&i| shells[i].contains(ls))
+                    .max_by_key(|&i| depth[i]);
+                if let Some(i) = parent {res[i].interiors_push(ls.clone());}
             }
         }
-        MultiPolygon::new(res)
+        Ok(MultiPolygon::new(res))
     }
 
-    pub(crate) fn apply_vertex_queue_rounded(&self, vertex_queue: &VertexQueue, offset_distance: f64) -> MultiPolygon{
-        let orient = self.get_orientation();
-        let mut res = Vec::new();
-        let mut lsv = Vec::new();
-        let mut crdv= Vec::new();
-        let mut cur_vidx = usize::MAX;
-        for (vidx, _, idx) in vertex_queue.iter(){
-            if vidx != cur_vidx{
-                if cur_vidx < usize::MAX {
-                    let mut ls = LineString::from(crdv);
-                    ls.close();
-                    lsv.push(ls);
-                }
-                cur_vidx = vidx;
-                crdv = Vec::new();
-            }
-            let time_left = offset_distance-self.ray_vector[idx].unwrap_time();
-            let (lray, rray) = self.ray_vector[idx].unwrap_base_ray();
-            let cray = self.ray_vector[idx].unwrap_ray();
-            if (lray.angle + cray.angle).norm() > (lray.angle - cray.angle).norm(){
-                let crd = cray.point_by_ratio(time_left);
-                crdv.push(crd);
-            }
-            else{
-                let mut left_normal;
-                let mut right_normal;
-                if orient{
-                    left_normal = Ray{origin: cray.origin, angle: (-lray.angle.1, lray.angle.0).into()};
-                    right_normal = Ray{origin: cray.origin, angle: (rray.angle.1, -rray.angle.0).into()};
-                }
-                else{
-                    left_normal = Ray{origin: cray.origin, angle: (lray.angle.1, -lray.angle.0).into()};
-                    right_normal = Ray{origin: cray.origin, angle: (-rray.angle.1, rray.angle.0).into()};
-                }
-                left_normal.normalize();
-                right_normal.normalize();
-                loop{
-                    let lcrd = left_normal.point_by_ratio(time_left);
-                    crdv.push(lcrd);
-                    left_normal = left_normal.rotate_by(if orient {0.1} else {-0.1});
-                    if orient && left_normal.orientation(&right_normal.point_by_ratio(1.)) == -1 {break;}
-                    if !orient && left_normal.orientation(&right_normal.point_by_ratio(1.)) == 1 {break;}
-                }
-                crdv.push(right_normal.point_by_ratio(time_left));
+    pub(crate) fn apply_vertex_queue(&self, vertex_queue: &VertexQueue, offset_distance: T, miter_limit: f64) -> Result<MultiPolygon<T>, BufferError>{
+        self.collect_wavefront(vertex_queue, offset_distance, |crdv, idx, time_left, orient| {
+            let cray = self.ray_vector[idx].unwrap_ray()?;
+            match self.corner_normals(idx, orient)?{
+                Some((left_normal, right_normal)) if miter_ratio(left_normal.angle, right_normal.angle, self.tolerance) > miter_limit => {
+                    crdv.extend(limited_miter_points(cray.origin, left_normal.angle, right_normal.angle, time_left, miter_limit));
+                },
+                _ => crdv.push(cray.point_by_ratio(time_left)),
             }
-        }
-        if cur_vidx < usize::MAX {
-            let mut ls = LineString::from(crdv);
-            ls.close();
-            lsv.push(ls);
-        }
-        for ls in &lsv{
-            if ls.winding_order() == Some(WindingOrder::CounterClockwise){
-                let p1: Polygon = Polygon::new(
-                    ls.clone(), vec![],
-                );
-                res.push(p1);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn apply_vertex_queue_rounded(&self, vertex_queue: &VertexQueue, offset_distance: T, arc_resolution: &ArcResolution) -> Result<MultiPolygon<T>, BufferError>{
+        self.collect_wavefront(vertex_queue, offset_distance, |crdv, idx, time_left, orient| {
+            let cray = self.ray_vector[idx].unwrap_ray()?;
+            match self.corner_normals(idx, orient)?{
+                Some((left_normal, right_normal)) => {
+                    crdv.extend(arc_points(left_normal, &right_normal, time_left, arc_resolution.step_angle(to_f64(time_left)), orient));
+                },
+                None => crdv.push(cray.point_by_ratio(time_left)),
             }
-        }
-        for ls in &lsv{
-            if ls.winding_order() == Some(WindingOrder::Clockwise){
-                for e in &mut res{
-                    if e.contains(ls){
-                        e.interiors_push(ls.clone());
-                        break;
-                    }
-                }
+            Ok(())
+        })
+    }
+
+    pub(crate) fn apply_vertex_queue_beveled(&self, vertex_queue: &VertexQueue, offset_distance: T) -> Result<MultiPolygon<T>, BufferError>{
+        self.collect_wavefront(vertex_queue, offset_distance, |crdv, idx, time_left, orient| {
+            let cray = self.ray_vector[idx].unwrap_ray()?;
+            match self.corner_normals(idx, orient)?{
+                Some((left_normal, right_normal)) => {
+                    crdv.push(left_normal.point_by_ratio(time_left));
+                    crdv.push(right_normal.point_by_ratio(time_left));
+                },
+                None => crdv.push(cray.point_by_ratio(time_left)),
             }
-        }
-        MultiPolygon::new(res)
+            Ok(())
+        })
     }
 
-    pub(crate) fn get_vertex_queue(&self, time_elapsed: f64) -> VertexQueue{
+    pub(crate) fn get_vertex_queue(&self, time_elapsed: T) -> Result<VertexQueue, BufferError>{
         let mut ret = self.initial_vertex_queue.clone();
         for e in &self.event_queue{
-            if e.unwrap_time() <= time_elapsed{
-                Self::apply_event(&mut ret, e);
-                ret.cleanup();
+            if e.time <= time_elapsed{
+                Self::apply_event(&mut ret, e)?;
+                ret.cleanup()?;
             }
             else {break;}
         }
-        ret
+        Ok(ret)
     }
 
-    fn get_orientation(&self) -> bool{
-        let iz_ray = self.ray_vector[0].unwrap_ray();
-        let iz_left = self.ray_vector[0].unwrap_base_ray().0;
-        iz_left.orientation(&iz_ray.point_by_ratio(1.)) == 1
+    /// Returns the normalized outward normals of the left and right edges of the given vertex if it is a convex vertex,
+    /// and `None` otherwise.
+    #[allow(clippy::type_complexity)]
+    fn corner_normals(&self, idx: usize, orient: bool) -> Result<Option<(Ray<T>, Ray<T>)>, BufferError>{
+        let (lray, rray) = self.ray_vector[idx].unwrap_base_ray()?;
+        let cray = self.ray_vector[idx].unwrap_ray()?;
+        if (lray.angle + cray.angle).norm() > (lray.angle - cray.angle).norm() {return Ok(None);}
+        let mut left_normal;
+        let mut right_normal;
+        if orient{
+            left_normal = Ray{origin: cray.origin, angle: (-lray.angle.1, lray.angle.0).into()};
+            right_normal = Ray{origin: cray.origin, angle: (rray.angle.1, -rray.angle.0).into()};
+        }
+        else{
+            left_normal = Ray{origin: cray.origin, angle: (lray.angle.1, -lray.angle.0).into()};
+            right_normal = Ray{origin: cray.origin, angle: (-rray.angle.1, rray.angle.0).into()};
+        }
+        left_normal.normalize();
+        right_normal.normalize();
+        Ok(Some((left_normal, right_normal)))
     }
 
-    fn find_split_vertex(cv: IndexType, vertex_queue: &VertexQueue, vertex_vector: &Vec<VertexType>, is_init: bool, orient: bool) -> Vec<(f64, Coordinate, IndexType, usize)>{
+    fn get_orientation(&self) -> Result<bool, BufferError>{
+        let Some(iz) = self.ray_vector.first() else {
+            return Err(BufferError::InternalInconsistency("the skeleton has no vertex".to_string()));
+        };
+        let iz_ray = iz.unwrap_ray()?;
+        let iz_left = iz.unwrap_base_ray()?.0;
+        Ok(iz_left.orientation(&iz_ray.point_by_ratio(T::one())) == 1)
+    }
+
+    fn find_split_vertex(cv: IndexType, vertex_queue: &VertexQueue, vertex_vector: &[VertexType<T>], orient: bool, eps: f64) -> Result<Vec<SplitCandidate<T>>, BufferError>{
         let mut ret = Vec::new();
-        let cv_real = vertex_queue.get_real_index(cv);
-        let left_ray = vertex_vector[cv_real].unwrap_base_ray().0;
-        let right_ray = vertex_vector[cv_real].unwrap_base_ray().1;
-        if orient && fleq(left_ray.angle.outer_product(&right_ray.angle), 0.) {return ret;} // check if ver_vec[i] is a reflex vertex
-        if !orient && fgeq(left_ray.angle.outer_product(&right_ray.angle), 0.) {return ret;}
+        let cv_real = vertex_queue.get_real_index(cv)?;
+        let (left_ray, right_ray) = vertex_vector[cv_real].unwrap_base_ray()?;
+        let cv_ray = vertex_vector[cv_real].unwrap_ray()?;
+        if orient && cross_sign(left_ray.angle, right_ray.angle) <= 0 {return Ok(ret);} // check if ver_vec[i] is a reflex vertex
+        if !orient && cross_sign(left_ray.angle, right_ray.angle) >= 0 {return Ok(ret);}
         
-        for (_, sv, sv_real) in vertex_queue.iter(){
-            let srv = vertex_queue.rv(sv);
-            let srv_real = vertex_queue.get_real_index(srv);
-            if sv == cv || sv == vertex_queue.rv(cv) || srv == cv || srv == vertex_queue.lv(cv) {continue;}
-            let base_ray = vertex_vector[sv_real].unwrap_base_ray().1;
-            let left_intersection = if left_ray.is_parallel(&base_ray) {Default::default()} else {left_ray.intersect(&base_ray)};
-            let right_intersection = if right_ray.is_parallel(&base_ray) {Default::default()} else {right_ray.intersect(&base_ray)};
-            let real_intersection = if left_ray.is_parallel(&base_ray) {
-                let ri_ray = right_ray.bisector(&base_ray.reverse(), right_intersection, !orient);
-                if !ri_ray.is_intersect(&vertex_vector[cv_real].unwrap_ray()) {continue;}
-                ri_ray.intersect(&vertex_vector[cv_real].unwrap_ray())
+        for e in vertex_queue.iter(){
+            let (_, sv, sv_real) = e?;
+            let srv = vertex_queue.rv(sv)?;
+            if sv == cv || sv == vertex_queue.rv(cv)? || srv == cv || srv == vertex_queue.lv(cv)? {continue;}
+            let base_ray = vertex_vector[sv_real].unwrap_base_ray()?.1;
+            let zero = Coordinate(T::zero(), T::zero());
+            let left_intersection = if left_ray.is_parallel_within(&base_ray, eps) {zero} else {left_ray.intersect_within(&base_ray, eps)};
+            let right_intersection = if right_ray.is_parallel_within(&base_ray, eps) {zero} else {right_ray.intersect_within(&base_ray, eps)};
+            let real_intersection = if left_ray.is_parallel_within(&base_ray, eps) {
+                let ri_ray = right_ray.bisector(&base_ray.reverse(), right_intersection, !orient, eps);
+                if !ri_ray.is_intersect_within(&cv_ray, eps) {continue;}
+                ri_ray.intersect_within(&cv_ray, eps)
             } else{
-                let li_ray = left_ray.bisector(&base_ray, left_intersection, orient);
-                if !li_ray.is_intersect(&vertex_vector[cv_real].unwrap_ray()) {continue;}
-                li_ray.intersect(&vertex_vector[cv_real].unwrap_ray())
+                let li_ray = left_ray.bisector(&base_ray, left_intersection, orient, eps);
+                if !li_ray.is_intersect_within(&cv_ray, eps) {continue;}
+                li_ray.intersect_within(&cv_ray, eps)
             };
-            if is_init {
-                if orient && base_ray.orientation(&real_intersection) < 0 {continue;}
-                if !orient && base_ray.orientation(&real_intersection) > 0 {continue;}
-            }
-            else{
-                if orient{
-                    if vertex_vector[sv_real].unwrap_ray().orientation(&real_intersection) >= 0 {continue;}
-                    if base_ray.orientation(&real_intersection) < 0 {continue;}
-                    if vertex_vector[srv_real].unwrap_ray().orientation(&real_intersection) < 0 {continue;}
-                }
-                else{
-                    if vertex_vector[sv_real].unwrap_ray().orientation(&real_intersection) <= 0 {continue;}
-                    if base_ray.orientation(&real_intersection) > 0 {continue;}
-                    if vertex_vector[srv_real].unwrap_ray().orientation(&real_intersection) > 0 {continue;}
-                }
-            }
-            let dist = real_intersection.dist_ray(&right_ray);
+            if orient && base_ray.orientation(&real_intersection) < 0 {continue;}
+            if !orient && base_ray.orientation(&real_intersection) > 0 {continue;}
+            let dist = ensure_finite(real_intersection.dist_ray(&right_ray), "the time of a split event")?;
             ret.push((dist, real_intersection, sv, sv_real));
         }
-        ret.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        if !is_init && ret.len() != 0 {ret = vec![ret[0]];}
-        ret
+        ret.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Ok(ret)
     }
 
-    fn make_split_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline>, vertex_vector: &Vec<VertexType>, orient: bool){
-        let resv = Self::find_split_vertex(cv, vertex_queue, vertex_vector, true, orient);
-        let cv_real = vertex_queue.get_real_index(cv);
+    fn make_split_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline<T>>, vertex_vector: &[VertexType<T>], orient: bool, eps: f64) -> Result<(), BufferError>{
+        let resv = Self::find_split_vertex(cv, vertex_queue, vertex_vector, orient, eps)?;
+        let cv_real = vertex_queue.get_real_index(cv)?;
         for (time, location, _, _) in resv{
-            event_pq.insert(Timeline::SplitEvent { time: time, location: location, anchor_vertex: cv, anchor_real: cv_real, });
+            event_pq.insert(Timeline::SplitEvent { time, location, anchor_vertex: cv, anchor_real: cv_real, });
         }
+        Ok(())
     }
 
-    fn make_shrink_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline>, vertex_vector: &Vec<VertexType>, is_init: bool){
+    fn make_shrink_event(cv: IndexType, vertex_queue: &VertexQueue, event_pq: &mut PriorityQueue<Timeline<T>>, vertex_vector: &[VertexType<T>], is_init: bool, eps: f64) -> Result<(), BufferError>{
         let mut lv = cv;
-        if vertex_queue.rv(cv) == vertex_queue.lv(cv) {return;}
+        if vertex_queue.rv(cv)? == vertex_queue.lv(cv)? {return Ok(());}
         for _ in 0..2{
-            let rv = vertex_queue.rv(lv);
-            let lv_real = vertex_queue.get_real_index(lv);
-            let rv_real = vertex_queue.get_real_index(rv);
-            let lv_ray = vertex_vector[lv_real].unwrap_ray();
-            let rv_ray = vertex_vector[rv_real].unwrap_ray();
-            if lv_ray.is_intersect(&rv_ray){
-                let cp = lv_ray.intersect(&rv_ray);
-                let dist = cp.dist_ray(&vertex_vector[lv_real].unwrap_base_ray().0);
-                let tie_break = lv_ray.origin.dist_coord(&rv_ray.origin);
-                event_pq.insert(Timeline::ShrinkEvent { time: dist, location: cp, left_vertex: lv, right_vertex: rv, left_real: lv_real, right_real: rv_real, tie_break: tie_break });
+            let rv = vertex_queue.rv(lv)?;
+            let lv_real = vertex_queue.get_real_index(lv)?;
+            let rv_real = vertex_queue.get_real_index(rv)?;
+            let lv_ray = vertex_vector[lv_real].unwrap_ray()?;
+            let rv_ray = vertex_vector[rv_real].unwrap_ray()?;
+            if lv_ray.is_intersect_within(&rv_ray, eps){
+                let cp = lv_ray.intersect_within(&rv_ray, eps);
+                let dist = ensure_finite(cp.dist_ray(&vertex_vector[lv_real].unwrap_base_ray()?.0), "the time of a shrink event")?;
+                event_pq.insert(Timeline::ShrinkEvent { time: dist, location: cp, left_vertex: lv, right_vertex: rv, left_real: lv_real, right_real: rv_real });
             }
             if is_init {break;}
-            lv = vertex_queue.lv(cv);
+            lv = vertex_queue.lv(cv)?;
         }
+        Ok(())
     }
 
-    fn apply_event(vertex_queue: &mut VertexQueue, event: &Event) -> (Option<IndexType>, Option<IndexType>){
-        if let Event::VertexEvent { merge_from, merge_to, .. } = event{
-            let merge_from = IndexType::PointerIndex(*merge_from);
-            let merge_to = IndexType::RealIndex(*merge_to);
-            let cv = vertex_queue.remove_and_set(merge_from, merge_to);
-            if vertex_queue.lv(cv) == vertex_queue.rv(cv){
-                let lv = vertex_queue.lv(cv);
-                vertex_queue.content[lv.get_index()].done = true;
-                vertex_queue.content[cv.get_index()].done = true;
-                return (Some(vertex_queue.content[vertex_queue.lv(cv).get_index()].index), None);
-            }
-            return (Some(cv), None);
-        }
-        if let Event::EdgeEvent{ split_from, split_into, split_to_left, split_to_right, ..} = event{
-            let split_from = IndexType::PointerIndex(*split_from);
-            let split_into = IndexType::PointerIndex(*split_into);
-            let split_to_left = IndexType::RealIndex(*split_to_left);
-            let split_to_right = IndexType::RealIndex(*split_to_right);
-            let ret = vertex_queue.split_and_set(split_from, split_into, split_to_left, split_to_right);
-            vertex_queue.cleanup();
-            return (Some(ret.0), Some(ret.1));
+    fn apply_event(vertex_queue: &mut VertexQueue, event: &Event<T>) -> Result<Vec<IndexType>, BufferError>{
+        vertex_queue.reconnect(&event.removed, &event.created)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn skeleton_of_polygon(input_polygon: &Polygon<T>, orient: bool, eps: f64) -> Result<Self, BufferError>{
+        Self::skeleton_of_polygon_vector(std::slice::from_ref(input_polygon), orient, eps)
+    }
+
+    /// Constructs the straight skeleton of the given polygons, with exact rational arithmetic if the `exact` feature is enabled.
+    /// `eps` is the tolerance of the comparisons, which should be the one in the range where the polygons are normalized.
+    ///
+    /// If the construction runs into an inconsistent state, it is retried with the input vertices perturbed by the distances
+    /// in [PERTURBATIONS] in turn, and the first one which succeeds is recorded in the result. (See [Skeleton::report()].)
+    /// The first error is returned if every retry fails as well.
+    pub(crate) fn skeleton_of_polygon_vector(input_polygon_vector: &[Polygon<T>], orient: bool, eps: f64) -> Result<Self, BufferError>{
+        Self::with_skeleton_of_polygon_vector(input_polygon_vector, orient, eps, Ok)
+    }
+
+    /// Constructs the straight skeleton of the given polygons as [skeleton_of_polygon_vector()][Self::skeleton_of_polygon_vector]
+    /// does, and returns the result of `f` applied to it. The construction is retried on the perturbed input if `f` returns
+    /// [BufferError::InternalInconsistency] as well, e.g. if the offset assembled from the skeleton turns out to be invalid.
+    pub(crate) fn with_skeleton_of_polygon_vector<R, F>(input_polygon_vector: &[Polygon<T>], orient: bool, eps: f64, f: F) -> Result<R, BufferError>
+    where
+        F: Fn(Self) -> Result<R, BufferError>,
+    {
+        let (input_polygon_vector, separation) = &prepare_polygons(input_polygon_vector, eps)?;
+        let err = match Self::try_skeleton_of_prepared_polygons(input_polygon_vector, orient, eps).and_then(|skel| f(Self{separation: *separation, ..skel})){
+            Err(e @ BufferError::InternalInconsistency(_)) => e,
+            res => return res,
+        };
+        for (seed, ratio) in PERTURBATIONS.into_iter().enumerate(){
+            let distance = ratio*eps;
+            let perturbed = perturb_polygons(input_polygon_vector, distance, seed);
+            let res = Self::try_skeleton_of_prepared_polygons(&perturbed, orient, eps)
+                .and_then(|skel| f(Self{perturbation: Some(distance), separation: *separation, ..skel}));
+            if let Ok(res) = res {return Ok(res);}
         }
+        Err(err)
+    }
 
-        (None, None)
+    /// Returns the report of how the input was modified to construct this skeleton, i.e. the distance by which the input vertices
+    /// were perturbed and the distance by which the touching vertices were separated. (See [BufferReport].)
+    pub(crate) fn report(&self) -> BufferReport{
+        BufferReport{perturbation: self.perturbation, separation: self.separation}
     }
 
-    pub(crate) fn skeleton_of_polygon(input_polygon: &Polygon, orient: bool) -> Self{
-        Self::skeleton_of_polygon_vector(&vec![input_polygon.clone()], orient)
+    /// Constructs the straight skeleton of the given (prepared) polygons with the arithmetic selected by the `exact` feature.
+    fn try_skeleton_of_prepared_polygons(input_polygon_vector: &Vec<Polygon<T>>, orient: bool, eps: f64) -> Result<Self, BufferError>{
+        #[cfg(feature = "exact")]
+        return exact::skeleton_of_prepared_polygons(input_polygon_vector, orient, eps);
+        #[cfg(not(feature = "exact"))]
+        Self::skeleton_of_prepared_polygons(input_polygon_vector, orient, eps)
     }
 
-    pub(crate) fn skeleton_of_polygon_vector(input_polygon_vector: &Vec<Polygon>, orient: bool) -> Self{
-        let mut vertex_vector = VertexType::initialize_from_polygon_vector(input_polygon_vector, orient);
+    #[cfg_attr(feature = "exact", allow(dead_code))]
+    fn skeleton_of_prepared_polygons(input_polygon_vector: &Vec<Polygon<T>>, orient: bool, eps: f64) -> Result<Self, BufferError>{
+        let mut vertex_vector = VertexType::initialize_from_polygon_vector(input_polygon_vector, orient, eps)?;
         let mut event_pq = PriorityQueue::new();
         let mut event_queue = Vec::new();
         let mut vertex_queue = VertexQueue::new();
         vertex_queue.initialize_from_polygon_vector(input_polygon_vector);
         let initial_vertex_queue = vertex_queue.clone();
         // make initial PQ
-        for (_, cv, _) in vertex_queue.iter(){
-            Self::make_shrink_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, true);
-            Self::make_split_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, orient);
+        for e in vertex_queue.iter(){
+            let (_, cv, _) = e?;
+            Self::make_shrink_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, true, eps)?;
+            Self::make_split_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, orient, eps)?;
         }
 
-        while !event_pq.is_empty() {
-            let x = event_pq.pop().unwrap();
-            if let Timeline::ShrinkEvent { time, location, left_vertex, right_vertex, left_real, right_real, .. } = x{
-                if vertex_queue.content[left_vertex.get_index()].done || vertex_queue.content[right_vertex.get_index()].done || vertex_queue.get_real_index(left_vertex) != left_real || vertex_queue.get_real_index(right_vertex) != right_real {
-                    continue;
-                }
-                let new_index = vertex_vector.len();
-                let left_ray = vertex_vector[left_real].unwrap_base_ray().0;
-                let right_ray = vertex_vector[right_real].unwrap_base_ray().1;
-                vertex_vector[left_real].set_parent(new_index);
-                vertex_vector[right_real].set_parent(new_index);
-                let new_event = Event::VertexEvent { time: time, merge_from: left_vertex.get_index(), merge_to: new_index };
-                let new_vertex = VertexType::new_tree_vertex(location, left_ray, right_ray, orient);
-                vertex_vector.push(new_vertex);
-                match Self::apply_event(&mut vertex_queue, &new_event){
-                    (Some(IndexType::RealIndex(rv)), None) => {
-                        vertex_vector[rv].set_parent(new_index);
-                        vertex_vector[new_index] = VertexType::new_root_vertex(vertex_vector[new_index].unwrap_location(), vertex_vector[new_index].unwrap_time());
-                    },
-                    (Some(cv), None) => {
-                        Self::make_shrink_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, false);
-                    },
-                    _ => panic!("Expected Vertex Event"),
+        while let Some(x) = event_pq.pop() {
+            let (time, location) = match x{
+                Timeline::ShrinkEvent { time, location, left_vertex, right_vertex, left_real, right_real } => {
+                    if vertex_queue.content[left_vertex.get_index()?].done || vertex_queue.content[right_vertex.get_index()?].done || vertex_queue.get_real_index(left_vertex)? != left_real || vertex_queue.get_real_index(right_vertex)? != right_real {
+                        continue;
+                    }
+                    (time, location)
+                },
+                Timeline::SplitEvent { time, location, anchor_vertex, anchor_real } => {
+                    if vertex_queue.content[anchor_vertex.get_index()?].done || vertex_queue.get_real_index(anchor_vertex)? != anchor_real {
+                        continue;
+                    }
+                    (time, location)
+                },
+            };
+            let Some(new_event) = Self::resolve_event(time, location, &vertex_queue, &mut vertex_vector, orient, eps)? else {continue};
+            let new_nodes = Self::apply_event(&mut vertex_queue, &new_event)?;
+            Self::set_parents(&new_event, &new_nodes, location, &vertex_queue, &mut vertex_vector)?;
+            vertex_queue.cleanup()?;
+            for cv in new_nodes{
+                if vertex_queue.content[cv.get_index()?].done {continue;}
+                Self::make_shrink_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, false, eps)?;
+                Self::make_split_event(cv, &vertex_queue, &mut event_pq, &vertex_vector, orient, eps)?;
+                if vertex_vector[vertex_queue.get_real_index(cv)?].unwrap_ray()?.is_degenerated(eps){
+                    // The edges of the new vertex are opposite and overlap, which collapse up to the nearer neighbor at this instant.
+                    for nv in [vertex_queue.lv(cv)?, vertex_queue.rv(cv)?]{
+                        let nv_real = vertex_queue.get_real_index(nv)?;
+                        let location = vertex_vector[nv_real].unwrap_ray()?.point_by_ratio(time - vertex_vector[nv_real].unwrap_time());
+                        event_pq.insert(Timeline::SplitEvent { time, location, anchor_vertex: nv, anchor_real: nv_real });
+                    }
                 }
-                event_queue.push(new_event);
             }
-            else if let Timeline::SplitEvent { time, location, anchor_vertex, anchor_real } = x{
-                if vertex_queue.content[anchor_vertex.get_index()].done || vertex_queue.get_real_index(anchor_vertex) != anchor_real {
-                    continue;
-                }
-                vertex_queue.cleanup();
-                let rv = Self::find_split_vertex(anchor_vertex, &vertex_queue, &vertex_vector, false, orient);
-                if rv.len() == 1 && feq(rv[0].0, time) && rv[0].1.eq(&location) {
-                    let new_index1 = vertex_vector.len();
-                    let new_index2 = new_index1 + 1;
-                    let new_split_vertex = VertexType::new_split_vertex(anchor_real, location, new_index1, new_index2, vertex_vector[anchor_real].unwrap_time());
-                    let new_tree_vertex1 = VertexType::new_tree_vertex(location, vertex_vector[anchor_real].unwrap_base_ray().0, vertex_vector[rv[0].3].unwrap_base_ray().1, orient);
-                    let new_tree_vertex2 = VertexType::new_tree_vertex(location, vertex_vector[rv[0].3].unwrap_base_ray().1.reverse(), vertex_vector[anchor_real].unwrap_base_ray().1, orient);
-                    vertex_vector.push(new_tree_vertex1);
-                    vertex_vector.push(new_tree_vertex2);
-                    vertex_vector.push(new_split_vertex);
-                    let new_event = Event::EdgeEvent { time, split_from: anchor_vertex.get_index(), split_into: rv[0].2.get_index(), split_to_left: new_index1, split_to_right: new_index2 };
-                    match Self::apply_event(&mut vertex_queue, &new_event){
-                        (Some(cv1), Some(cv2)) => {
-                            vertex_vector[anchor_real].set_parent(new_index2+1);
-                            Self::make_shrink_event(cv1, &vertex_queue, &mut event_pq, &vertex_vector, false);
-                            Self::make_shrink_event(cv2, &vertex_queue, &mut event_pq, &vertex_vector, false);
-                        },
-                        _ => panic!("Expected Edge Event"),
-                    }
-                    event_queue.push(new_event); 
+            event_queue.push(new_event);
+        }
+        Ok(Self { input_polygon_vector: input_polygon_vector.clone(), ray_vector: vertex_vector, event_queue, initial_vertex_queue, perturbation: None, separation: None, tolerance: eps })
+    }
+
+    /// Resolves the event at the given location and time: gathers the wavefront vertices which meet at the location and the
+    /// wavefront edges which pass through it, and makes the new vertices between them. (See [resolve_chains()].)
+    /// Returns `None` if the wavefront does not change at the location.
+    ///
+    /// The events which happen within the tolerance of this one are merged into it here: every vertex which reaches the location
+    /// within the tolerance at the given time is resolved at once, thus the events queued for it later become stale.
+    fn resolve_event(time: T, location: Coordinate<T>, vertex_queue: &VertexQueue, vertex_vector: &mut Vec<VertexType<T>>, orient: bool, eps: f64) -> Result<Option<Event<T>>, BufferError>{
+        let position = |real: usize| -> Result<Coordinate<T>, BufferError>{
+            Ok(vertex_vector[real].unwrap_ray()?.point_by_ratio(time - vertex_vector[real].unwrap_time()))
+        };
+        let mut at = Vec::new();
+        for e in vertex_queue.iter(){
+            let (_, cv, cv_real) = e?;
+            if position(cv_real)?.eq_within(&location, eps) {at.push(cv);}
+        }
+        if at.is_empty() {return Ok(None);}
+        let (mut chains, collapsed) = gather_chains(vertex_queue, &at)?;
+        // The ray along the given edge in the direction of the wavefront.
+        let edge = |(real, is_right): (usize, bool)| -> Result<Ray<T>, BufferError>{
+            let (left_ray, right_ray) = vertex_vector[real].unwrap_base_ray()?;
+            Ok(if is_right {right_ray} else {left_ray.reverse()})
+        };
+        let sign = if orient {1} else {-1};
+        let mut is_convex = true;
+        for c in &chains{
+            let (mut d1, mut d2) = (edge(c.in_edge)?.reverse(), edge(c.out_edge)?);
+            d1.normalize();
+            d2.normalize();
+            is_convex &= flt(d1.angle.outer_product(&d2.angle)*float(sign as f64), T::zero(), eps);
+        }
+        if !is_convex{
+            // Only a reflex (or straight) vertex can reach a wavefront edge.
+            let mut member = vec![false; vertex_queue.content.len()];
+            for cv in &at {member[cv.get_index()?] = true;}
+            for e in vertex_queue.iter(){
+                let (_, sv, sv_real) = e?;
+                let srv = vertex_queue.rv(sv)?;
+                if member[sv.get_index()?] || member[srv.get_index()?] {continue;}
+                let base_ray = vertex_vector[sv_real].unwrap_base_ray()?.1;
+                if fneq(location.dist_ray(&base_ray), time, eps) || base_ray.orientation(&location) != sign {continue;}
+                let direction = base_ray.angle/base_ray.angle.norm();
+                let (sp, srp) = (position(sv_real)?, position(vertex_queue.get_real_index(srv)?)?);
+                if fgt((location-sp).inner_product(&direction), T::zero(), eps) && fgt((srp-location).inner_product(&direction), T::zero(), eps){
+                    chains.push(Chain{nodes: Vec::new(), left: sv, right: srv, in_edge: (sv_real, true), out_edge: (sv_real, true)});
                 }
             }
-            vertex_queue.cleanup();
         }
-        Self { ray_vector: vertex_vector, event_queue: event_queue, initial_vertex_queue: initial_vertex_queue }
+        let mut order = Vec::new();
+        for (i, c) in chains.iter().enumerate(){
+            for (d, is_out) in [(edge(c.in_edge)?.angle*-T::one(), false), (edge(c.out_edge)?.angle, true)]{
+                let d = if orient {d} else {Coordinate(d.0, -d.1)};
+                order.push((Float::atan2(d.1, d.0), i, is_out));
+            }
+        }
+        order.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let mut groups: Vec<Vec<(usize, bool)>> = Vec::new();
+        for (i, &(angle, c, is_out)) in order.iter().enumerate(){
+            match groups.last_mut(){
+                Some(g) if feq(angle, order[i-1].0, eps) => g.push((c, is_out)),
+                _ => groups.push(vec![(c, is_out)]),
+            }
+        }
+        if groups.len() > 1 && feq(order[0].0 + float(std::f64::consts::TAU), order[order.len()-1].0, eps){
+            let first = groups.remove(0);
+            if let Some(g) = groups.last_mut() {g.extend(first);}
+        }
+        let (removed, created) = resolve_chains(&chains, &groups)?;
+        if removed.is_empty() && created.is_empty() && collapsed.is_empty() {return Ok(None);}
+        let edge_index = |(real, is_right): (usize, bool)| -> Result<usize, BufferError>{
+            let (left_edge, right_edge) = vertex_vector[real].unwrap_edges()?;
+            Ok(if is_right {right_edge} else {left_edge})
+        };
+        let mut new_vertices = Vec::new();
+        for &(a, b) in &created{
+            let edges = (edge_index(chains[a].in_edge)?, edge_index(chains[b].out_edge)?);
+            new_vertices.push(VertexType::new_tree_vertex(location, edge(chains[a].in_edge)?.reverse(), edge(chains[b].out_edge)?, edges, orient, eps)?);
+        }
+        let mut links = Vec::new();
+        for ((a, b), new_vertex) in created.into_iter().zip(new_vertices){
+            links.push((vertex_vector.len(), chains[a].left.get_index()?, chains[b].right.get_index()?));
+            vertex_vector.push(new_vertex);
+        }
+        let removed = removed.iter().chain(&collapsed).map(|cv| cv.get_index()).collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(Event{time, removed, created: links}))
     }
 
-    
+    /// Links the vertices removed by the given event to the skeleton node at its location, which is the new vertex if the event
+    /// makes only one, a split vertex of the new vertices if it makes more, and a root vertex otherwise.
+    fn set_parents(event: &Event<T>, new_nodes: &[IndexType], location: Coordinate<T>, vertex_queue: &VertexQueue, vertex_vector: &mut Vec<VertexType<T>>) -> Result<(), BufferError>{
+        let hub = match event.created.len(){
+            0 => {
+                vertex_vector.push(VertexType::new_root_vertex(location, event.time));
+                vertex_vector.len() - 1
+            },
+            1 => event.created[0].0,
+            _ => {
+                vertex_vector.push(VertexType::new_split_vertex(location, event.created.iter().map(|x| x.0).collect(), event.time));
+                vertex_vector.len() - 1
+            },
+        };
+        for &cv in &event.removed{
+            vertex_vector[vertex_queue.content[cv].index.get_real_index()?].set_parent(hub)?;
+        }
+        for (cv, &(nv, ..)) in new_nodes.iter().zip(&event.created){
+            if !vertex_queue.content[cv.get_index()?].done {continue;}
+            // The new vertex closes a cycle of two vertices, which meet at its location.
+            let lv = vertex_queue.get_real_index(vertex_queue.lv(*cv)?)?;
+            vertex_vector[lv].set_parent(nv)?;
+            vertex_vector[nv] = VertexType::new_root_vertex(location, event.time);
+        }
+        Ok(())
+    }
 
-    pub(crate) fn to_linestring(&self) -> Vec<LineString>{
-        fn dfs_helper(cur: usize, visit: &mut Vec<bool>, ret: &mut Vec<LineString>, ray_vector: &Vec<VertexType>){
-            if visit[cur] {return;}
+    /// Returns the arcs of the skeleton as line strings, where the unbounded arcs end at the given time after their start.
+    pub(crate) fn to_linestring(&self, ray_length: T) -> Result<Vec<LineString<T>>, BufferError>{
+        fn dfs_helper<T: GeoFloat>(cur: usize, visit: &mut Vec<bool>, ret: &mut Vec<LineString<T>>, ray_vector: &[VertexType<T>], ray_length: T) -> Result<(), BufferError>{
+            if visit[cur] {return Ok(());}
             visit[cur] = true;
-            match ray_vector[cur]{
-                VertexType::RootVertex { .. } => {return;},
-                VertexType::TreeVertex { parent, .. } => {
+            match &ray_vector[cur]{
+                VertexType::RootVertex { .. } => {},
+                &VertexType::TreeVertex { parent, .. } => {
                     if parent == usize::MAX{
-                        let ls = LineString(vec![ray_vector[cur].unwrap_location().into(), ray_vector[cur].unwrap_ray().point_by_ratio(5.).into()]);
+                        let ls = LineString(vec![ray_vector[cur].unwrap_location().into(), ray_vector[cur].unwrap_ray()?.point_by_ratio(ray_length).into()]);
                         ret.push(ls);
-                        return;
+                        return Ok(());
                     }
                     let ls = LineString(vec![ray_vector[cur].unwrap_location().into(), ray_vector[parent].unwrap_location().into()]);
                     ret.push(ls);
-                    dfs_helper(parent, visit, ret, ray_vector);
+                    dfs_helper(parent, visit, ret, ray_vector, ray_length)?;
                 },
-                VertexType::SplitVertex { split_left, split_right, .. } => {
-                    dfs_helper(split_left, visit, ret, ray_vector);
-                    dfs_helper(split_right, visit, ret, ray_vector);
+                VertexType::SplitVertex { split_into, .. } => {
+                    for &nv in split_into{
+                        dfs_helper(nv, visit, ret, ray_vector, ray_length)?;
+                    }
                 }
             }
+            Ok(())
         }
         let mut visit = vec![false;self.ray_vector.len()];
         let mut ret = Vec::new();
-        for (_, _, e) in self.initial_vertex_queue.iter(){
-            dfs_helper(e, &mut visit, &mut ret, &self.ray_vector);
+        for e in self.initial_vertex_queue.iter(){
+            let (_, _, e) = e?;
+            dfs_helper(e, &mut visit, &mut ret, &self.ray_vector, ray_length)?;
         }
-        ret
+        Ok(ret)
     }
 }
\ No newline at end of file
@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use geo::BoundingRect;
+use geo_types::{Coord, LineString, Polygon, MultiPolygon};
+
+use crate::error::BufferError;
+use crate::report::BufferReport;
+use crate::util::*;
+use crate::normalization::{normalized, Normalization};
+use super::{Skeleton, VertexType};
+
+/// This enumeration represents the kind of a node of a [StraightSkeleton], i.e. how many wavefront vertices start at the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkeletonNodeKind{
+    /// A single wavefront vertex starts at the node. The node is either an input vertex or a point where the wavefront vertices
+    /// which meet there merge into one.
+    Tree,
+    /// Several wavefront vertices start at the node, i.e. the wavefront splits there.
+    Split,
+    /// No wavefront vertex starts at the node, i.e. (a part of) the wavefront vanishes there.
+    Root,
+}
+
+/// This structure represents a vertex or an edge of the input of a [StraightSkeleton] by the index of the polygon, the index of the
+/// ring (`0` for the exterior ring and `i+1` for the `i`-th interior ring) and the index of the vertex in the ring.
+/// The edge of an index starts at the vertex of the same index and ends at the next one.
+///
+/// The indices refer to [StraightSkeleton::input()], which may differ from the given polygons since they are cleaned before
+/// the straight skeleton is constructed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InputIndex{
+    /// The index of the polygon.
+    pub polygon: usize,
+    /// The index of the ring in the polygon.
+    pub ring: usize,
+    /// The index of the vertex (or the edge) in the ring.
+    pub index: usize,
+}
+
+/// The closed ring of a face of a straight skeleton, where each point is paired with the time when the wavefront passes through it
+/// and the index of its node. The nodes at the same location have the same index, and the points on the horizon have none.
+pub(crate) type FaceRing<T> = Vec<(Coord<T>, T, Option<usize>)>;
+
+/// Returns the index of each vertex of the given polygons in the order of the rings, with the index of the next vertex in its ring.
+fn input_vertices<T: BufferFloat>(input: &[Polygon<T>]) -> Vec<(InputIndex, usize)>{
+    let mut ret = Vec::new();
+    for (polygon, p) in input.iter().enumerate(){
+        for (ring, ls) in std::iter::once(p.exterior()).chain(p.interiors()).enumerate(){
+            let (offset, len) = (ret.len(), ls.0.len().saturating_sub(1));
+            ret.extend((0..len).map(|index| (InputIndex{polygon, ring, index}, offset + (index+1)%len)));
+        }
+    }
+    ret
+}
+
+/// This structure represents a node of a [StraightSkeleton]: a point where the wavefront vertices meet or start.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkeletonNode<T: BufferFloat = f64>{
+    location: Coord<T>,
+    time: T,
+    kind: SkeletonNodeKind,
+}
+
+impl<T: BufferFloat> SkeletonNode<T>{
+    /// Returns the location of the node.
+    pub fn location(&self) -> Coord<T>{
+        self.location
+    }
+
+    /// Returns the time of the event at the node, i.e. the distance which the wavefront has propagated when it reaches the node.
+    /// It is zero for the input vertices.
+    pub fn time(&self) -> T{
+        self.time
+    }
+
+    /// Returns the kind of the node.
+    pub fn kind(&self) -> SkeletonNodeKind{
+        self.kind
+    }
+}
+
+/// This structure represents an arc of a [StraightSkeleton], i.e. the trace of a single wavefront vertex, which moves along the
+/// bisector of two input edges from the node where it starts until the node where it meets other ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkeletonArc<T: BufferFloat = f64>{
+    start: usize,
+    end: Option<usize>,
+    velocity: Coord<T>,
+    left_edge: InputIndex,
+    right_edge: InputIndex,
+    input_vertex: Option<InputIndex>,
+}
+
+impl<T: BufferFloat> SkeletonArc<T>{
+    /// Returns the index of the node where the arc starts.
+    pub fn start(&self) -> usize{
+        self.start
+    }
+
+    /// Returns the index of the node where the arc ends, or `None` if the arc is unbounded, i.e. the wavefront vertex moves away
+    /// forever. (This happens only in the outward skeleton.)
+    pub fn end(&self) -> Option<usize>{
+        self.end
+    }
+
+    /// Returns the velocity of the wavefront vertex, i.e. the vertex is at `start + velocity * (t - start time)` at the time `t`.
+    ///
+    /// It is zero if the input edges of the vertex are opposite and overlap. Then the wavefront between them collapses at once,
+    /// and the arc is the segment where it collapses, whose ends have the same time.
+    pub fn velocity(&self) -> Coord<T>{
+        self.velocity
+    }
+
+    /// Returns the input edge on which the edge before the wavefront vertex lies, with respect to the orientation of the ring.
+    pub fn left_edge(&self) -> InputIndex{
+        self.left_edge
+    }
+
+    /// Returns the input edge on which the edge after the wavefront vertex lies, with respect to the orientation of the ring.
+    pub fn right_edge(&self) -> InputIndex{
+        self.right_edge
+    }
+
+    /// Returns the input vertex from which the wavefront vertex originates, or `None` if it is created at an event.
+    pub fn input_vertex(&self) -> Option<InputIndex>{
+        self.input_vertex
+    }
+}
+
+/// This structure represents the straight skeleton of (multi-)polygons as a graph of [SkeletonNode]s and [SkeletonArc]s,
+/// which are read-only.
+///
+/// The first nodes are the input vertices in the order of the rings in [StraightSkeleton::input()], thus the node of an index
+/// less than the number of the input vertices is the input vertex at the same position. Every other node is a point where
+/// the wavefront vertices meet.
+///
+/// # Example
+///
+/// ```
+/// use geo_buffer::{StraightSkeleton, SkeletonNodeKind};
+/// use geo::{Polygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![],
+/// );
+/// let skel = StraightSkeleton::of_polygon(&p1, true).unwrap();
+///
+/// assert_eq!(skel.arcs().len(), 5);
+/// let ridge: Vec<_> = skel.nodes().iter().filter(|n| n.kind() != SkeletonNodeKind::Tree || n.time() > 0.).collect();
+/// assert_eq!(ridge.len(), 2);
+/// assert!(ridge.iter().all(|n| n.time() == 1.));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StraightSkeleton<T: BufferFloat = f64>{
+    input: Vec<Polygon<T>>,
+    orientation: bool,
+    nodes: Vec<SkeletonNode<T>>,
+    arcs: Vec<SkeletonArc<T>>,
+    report: BufferReport,
+}
+
+impl<T: BufferFloat> StraightSkeleton<T>{
+    /// Constructs the straight skeleton of the given polygon.
+    ///
+    /// `orientation` determines the region where the straight skeleton is created: `true` for the inward region of the polygon,
+    /// and `false` for the outward region.
+    pub fn of_polygon(input_polygon: &Polygon<T>, orientation: bool) -> Result<Self, BufferError>{
+        Self::of_polygon_vector(std::slice::from_ref(input_polygon), orientation)
+    }
+
+    /// Constructs the straight skeleton of the given multi-polygon. (See [StraightSkeleton::of_polygon()].)
+    pub fn of_multi_polygon(input_multi_polygon: &MultiPolygon<T>, orientation: bool) -> Result<Self, BufferError>{
+        Self::of_polygon_vector(&input_multi_polygon.0, orientation)
+    }
+
+    /// Constructs the straight skeleton of the given polygons in the well-conditioned range, and transforms it back.
+    /// (See [normalized()].)
+    fn of_polygon_vector(input_polygon_vector: &[Polygon<T>], orientation: bool) -> Result<Self, BufferError>{
+        normalized(&MultiPolygon::new(input_polygon_vector.to_vec()), None, |input, normalization, eps| {
+            Ok(Self::of_normalized_polygon_vector(&input.0, orientation, eps)?.reverted(normalization))
+        })
+    }
+
+    /// Constructs the straight skeleton of the given polygons, which are already transformed into the well-conditioned range,
+    /// with the given tolerance in that range.
+    pub(crate) fn of_normalized_polygon_vector(input_polygon_vector: &[Polygon<T>], orientation: bool, eps: f64) -> Result<Self, BufferError>{
+        let skel = Skeleton::skeleton_of_polygon_vector(input_polygon_vector, orientation, eps)?;
+        Self::from_skeleton(&skel, orientation)
+    }
+
+    /// Returns the straight skeleton transformed back from the well-conditioned range by the given transformation. The velocities
+    /// do not change since the times are scaled as well as the locations.
+    fn reverted(self, normalization: &Normalization<T>) -> Self{
+        let scale = normalization.scale();
+        let nodes = self.nodes.into_iter()
+            .map(|n| SkeletonNode{location: normalization.revert_coord(n.location), time: n.time*scale, ..n})
+            .collect();
+        Self{input: normalization.revert(&MultiPolygon::new(self.input)).0, nodes, report: self.report.scaled(to_f64(scale)), ..self}
+    }
+
+    fn from_skeleton(skel: &Skeleton<T>, orientation: bool) -> Result<Self, BufferError>{
+        let input = skel.input_polygon_vector.clone();
+        let input_index = input_vertices(&input).into_iter().map(|x| x.0).collect::<Vec<_>>();
+        let input_edge = |i: usize| input_index.get(i).copied().ok_or_else(|| {
+            BufferError::InternalInconsistency(format!("a wavefront vertex lies on the input edge {i} which does not exist"))
+        });
+
+        // The new vertices of a split are at the location of the split, thus they start at the node of the split.
+        let ray_vector = &skel.ray_vector;
+        let mut hub = (0..ray_vector.len()).collect::<Vec<_>>();
+        for (i, v) in ray_vector.iter().enumerate(){
+            if let VertexType::SplitVertex { split_into, .. } = v{
+                for &nv in split_into {hub[nv] = i;}
+            }
+        }
+        let mut node_index = vec![usize::MAX; ray_vector.len()];
+        let mut nodes = Vec::new();
+        for (i, v) in ray_vector.iter().enumerate(){
+            if hub[i] != i {continue;}
+            let kind = match v{
+                VertexType::TreeVertex { .. } => SkeletonNodeKind::Tree,
+                VertexType::SplitVertex { .. } => SkeletonNodeKind::Split,
+                VertexType::RootVertex { .. } => SkeletonNodeKind::Root,
+            };
+            node_index[i] = nodes.len();
+            nodes.push(SkeletonNode{location: v.unwrap_location().into(), time: v.unwrap_time(), kind});
+        }
+        for i in 0..ray_vector.len(){
+            node_index[i] = node_index[hub[i]];
+        }
+        let mut arcs = Vec::new();
+        for (i, v) in ray_vector.iter().enumerate(){
+            let &VertexType::TreeVertex { axis, left_edge, right_edge, parent, .. } = v else {continue};
+            arcs.push(SkeletonArc{
+                start: node_index[i],
+                end: (parent != usize::MAX).then(|| node_index[parent]),
+                velocity: axis.angle.into(),
+                left_edge: input_edge(left_edge)?,
+                right_edge: input_edge(right_edge)?,
+                input_vertex: input_index.get(i).copied(),
+            });
+        }
+        Ok(Self{input, orientation, nodes, arcs, report: skel.report()})
+    }
+
+    /// Returns the polygons from which the straight skeleton is constructed, i.e. the given polygons whose repeated points and
+    /// collinear vertices are removed, overlapping members are merged, and rings are oriented. The exterior rings are oriented
+    /// counter-clockwise and the interior rings clockwise. If the input was perturbed (see [StraightSkeleton::report()]),
+    /// these are the perturbed polygons.
+    pub fn input(&self) -> &[Polygon<T>]{
+        &self.input
+    }
+
+    /// Returns `true` if the straight skeleton lies in the inward region of the input, and `false` if it lies in the outward region.
+    pub fn orientation(&self) -> bool{
+        self.orientation
+    }
+
+    /// Returns the nodes of the straight skeleton.
+    pub fn nodes(&self) -> &[SkeletonNode<T>]{
+        &self.nodes
+    }
+
+    /// Returns the arcs of the straight skeleton.
+    pub fn arcs(&self) -> &[SkeletonArc<T>]{
+        &self.arcs
+    }
+
+    /// Returns the [BufferReport] which describes how the straight skeleton was constructed, e.g. whether the input was perturbed.
+    pub fn report(&self) -> BufferReport{
+        self.report
+    }
+
+    /// Returns the faces of the straight skeleton, i.e. the region swept by the wavefront of each edge of each input ring,
+    /// in the order of the edges in [StraightSkeleton::input()]. The faces partition the region where the skeleton lies.
+    ///
+    /// Each face is bounded by its input edge and the arcs whose wavefront vertices lie on the edge, and its exterior ring is
+    /// oriented counter-clockwise. The faces of the outward skeleton are unbounded, thus they are truncated where the wavefront is
+    /// at the time of the last event plus the extent of the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geo_buffer::StraightSkeleton;
+    /// use geo::{Area, Polygon, LineString};
+    ///
+    /// let p1 = Polygon::new(
+    ///     LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![],
+    /// );
+    /// let faces = StraightSkeleton::of_polygon(&p1, true).unwrap().faces().unwrap();
+    ///
+    /// assert_eq!(faces.len(), 4);
+    /// assert_eq!(faces[0].1.unsigned_area(), 3.);
+    /// assert_eq!(faces[1].1.unsigned_area(), 1.);
+    /// ```
+    pub fn faces(&self) -> Result<Vec<(InputIndex, Polygon<T>)>, BufferError>{
+        let faces = self.face_rings()?.into_iter()
+            .map(|(e, ring)| (e, Polygon::new(LineString::from(ring.into_iter().map(|x| x.0).collect::<Vec<_>>()), vec![])));
+        Ok(faces.collect())
+    }
+
+    /// Returns the ring of each face of [StraightSkeleton::faces()].
+    pub(crate) fn face_rings(&self) -> Result<Vec<(InputIndex, FaceRing<T>)>, BufferError>{
+        let inconsistent = |e: &InputIndex| BufferError::InternalInconsistency(format!("the face of the input edge {e:?} is not closed"));
+        let mut incident: HashMap<InputIndex, Vec<usize>> = HashMap::new();
+        for (i, arc) in self.arcs.iter().enumerate(){
+            incident.entry(arc.left_edge).or_default().push(i);
+            if arc.right_edge != arc.left_edge {incident.entry(arc.right_edge).or_default().push(i);}
+        }
+        let extent = MultiPolygon::new(self.input.clone()).bounding_rect().map_or(T::zero(), |r| num_traits::Float::max(r.width(), r.height()));
+        let horizon = self.nodes.iter().fold(T::zero(), |acc, n| num_traits::Float::max(acc, n.time)) + extent;
+        // The nodes at the same location are identified with the first one of them, so that the faces on both sides of an arc
+        // of zero length agree on its nodes.
+        let mut order = (0..self.nodes.len()).collect::<Vec<_>>();
+        let location = |i: usize| (self.nodes[i].location.x, self.nodes[i].location.y);
+        order.sort_by(|&a, &b| location(a).partial_cmp(&location(b)).unwrap_or(std::cmp::Ordering::Equal).then(a.cmp(&b)));
+        let mut canonical = (0..self.nodes.len()).collect::<Vec<_>>();
+        for w in order.windows(2){
+            if location(w[0]) == location(w[1]) {canonical[w[1]] = canonical[w[0]];}
+        }
+        // The point where the given arc ends, or where it crosses the horizon if it is unbounded.
+        let point = |node: usize| (self.nodes[node].location, self.nodes[node].time, Some(canonical[node]));
+        let end_point = |arc: &SkeletonArc<T>| match arc.end{
+            Some(end) => point(end),
+            None => {
+                let start = self.nodes[arc.start];
+                (start.location + arc.velocity*(horizon - start.time), horizon, None)
+            },
+        };
+        // The face lies to the left of the boundary in the inward skeleton and to the right in the outward one. Thus the boundary
+        // continues along the arc which comes first from the reverse of the arriving direction clockwise (resp. counter-clockwise).
+        let sign = if self.orientation {T::one()} else {-T::one()};
+        let turn = |back: Coord<T>, d: Coord<T>| {
+            let a = (T::atan2(back.y, back.x) - T::atan2(d.y, d.x))*sign;
+            let tau = float::<T>(std::f64::consts::TAU);
+            if a <= T::zero() {a + tau} else {a}
+        };
+
+        let mut ret = Vec::new();
+        for (edge, (e, next)) in input_vertices(&self.input).into_iter().enumerate(){
+            let arcs = incident.get(&e).map_or(&[][..], |v| &v[..]);
+            let mut used = vec![false; arcs.len()];
+            let mut crdv = vec![point(edge), point(next)];
+            let mut cur = Some(next);
+            while cur != Some(edge){
+                let (last, back) = (crdv[crdv.len()-1].0, crdv[crdv.len()-2].0 - crdv[crdv.len()-1].0);
+                // The candidates are the arcs incident to the current node, or the unbounded arcs if the boundary is at the horizon.
+                let candidates = (0..arcs.len()).filter(|&k| !used[k] && match cur{
+                    Some(c) => self.arcs[arcs[k]].start == c || self.arcs[arcs[k]].end == Some(c),
+                    None => self.arcs[arcs[k]].end.is_none(),
+                });
+                let direction = |k: usize| {
+                    let arc = &self.arcs[arcs[k]];
+                    if cur == Some(arc.start) {end_point(arc).0 - last} else {self.nodes[arc.start].location - last}
+                };
+                let Some(k) = candidates.min_by(|&a, &b| turn(back, direction(a)).partial_cmp(&turn(back, direction(b))).unwrap_or(std::cmp::Ordering::Equal)) else {
+                    return Err(inconsistent(&e));
+                };
+                used[k] = true;
+                let arc = &self.arcs[arcs[k]];
+                if cur == Some(arc.start){
+                    crdv.push(end_point(arc));
+                    cur = arc.end;
+                }
+                else{
+                    if cur.is_none() {crdv.push(end_point(arc));}
+                    crdv.push(point(arc.start));
+                    cur = Some(arc.start);
+                }
+            }
+            crdv.dedup_by(|a, b| a.0 == b.0);
+            if !self.orientation {crdv.reverse();}
+            ret.push((e, crdv));
+        }
+        Ok(ret)
+    }
+}
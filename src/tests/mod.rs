@@ -0,0 +1,869 @@
+use geo::{Area, BoundingRect, Contains};
+use geo_types::{LineString, MultiLineString, Polygon, MultiPolygon, Point, MultiPoint, Line, Rect, Triangle, Geometry, GeometryCollection};
+
+use crate::*;
+
+fn assert_area(mp: &MultiPolygon, expected: f64, tolerance: f64){
+    let area = mp.unsigned_area();
+    assert!(f64::abs(area - expected) < tolerance, "expected area {} but {}", expected, area);
+}
+
+#[test]
+fn line_string_flat_cap(){
+    let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+    let res = buffer_line_string(&ls, 1., EndCapStyle::Flat);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 8., 1e-6);
+    assert!(res.contains(&Point::new(2.9, -0.9)));
+    assert!(!res.contains(&Point::new(-0.1, 0.)));
+}
+
+#[test]
+fn line_string_square_cap(){
+    let ls = LineString::from(vec![(0., 0.), (4., 0.)]);
+    let res = buffer_line_string(&ls, 1., EndCapStyle::Square);
+    assert_area(&res, 12., 1e-6);
+    assert!(res.contains(&Point::new(-0.9, 0.9)));
+}
+
+#[test]
+fn line_string_round_cap(){
+    let ls = LineString::from(vec![(0., 0.), (4., 0.)]);
+    let res = buffer_line_string(&ls, 1., EndCapStyle::Round);
+    assert_area(&res, 8. + std::f64::consts::PI, 1e-2);
+    assert!(res.contains(&Point::new(-0.9, 0.)));
+    assert!(!res.contains(&Point::new(-0.8, 0.8)));
+}
+
+#[test]
+fn line_string_turning_back(){
+    let ls = LineString::from(vec![(0., 0.), (4., 0.), (2., 0.)]);
+    let res = buffer_line_string(&ls, 1., EndCapStyle::Flat);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 10., 1e-6);
+}
+
+#[test]
+fn line_string_degenerated(){
+    let ls = LineString::from(vec![(1., 1.), (1., 1.)]);
+    assert_eq!(buffer_line_string(&ls, 1., EndCapStyle::Flat).0.len(), 0);
+    assert_area(&buffer_line_string(&ls, 1., EndCapStyle::Square), 4., 1e-6);
+    assert_area(&buffer_line_string(&ls, 1., EndCapStyle::Round), std::f64::consts::PI, 1e-2);
+    let ls = LineString::from(vec![(0., 0.), (4., 0.)]);
+    assert_eq!(buffer_line_string(&ls, -1., EndCapStyle::Round).0.len(), 0);
+}
+
+#[test]
+fn multi_line_string_merged(){
+    let ls1 = LineString::from(vec![(0., 0.), (4., 0.)]);
+    let ls2 = LineString::from(vec![(2., -2.), (2., 2.)]);
+    let ls3 = LineString::from(vec![(10., 0.), (12., 0.)]);
+    let mls = MultiLineString::new(vec![ls1, ls2, ls3]);
+    let res = buffer_multi_line_string(&mls, 0.5, EndCapStyle::Flat);
+    assert_eq!(res.0.len(), 2);
+    assert_area(&res, 4. + 4. - 1. + 2., 1e-6);
+}
+
+#[test]
+fn point_buffers(){
+    let p = Point::new(1., 2.);
+    assert_area(&buffer_point(&p, 1.), 4., 1e-9);
+    assert_area(&buffer_point_rounded(&p, 1.), std::f64::consts::PI, 1e-2);
+    assert!(buffer_point_rounded(&p, 1.).contains(&Point::new(1.9, 2.)));
+    assert_eq!(buffer_point(&p, -1.).0.len(), 0);
+}
+
+#[test]
+fn multi_point_merged(){
+    let mp = MultiPoint::from(vec![(0., 0.), (1.5, 0.), (10., 10.)]);
+    let res = buffer_multi_point_rounded(&mp, 1.);
+    assert_eq!(res.0.len(), 2);
+    assert!(res.contains(&Point::new(0.75, 0.)));
+    let res = buffer_multi_point(&mp, 1.);
+    assert_eq!(res.0.len(), 2);
+    assert_area(&res, 4. + 4. - 0.5*2. + 4., 1e-6);
+}
+
+#[test]
+fn rect_and_triangle(){
+    let r = Rect::new((0., 0.), (4., 2.));
+    assert_area(&buffer_rect(&r, 1.), 6.*4., 1e-6);
+    assert_area(&buffer_rect(&r, -0.5), 3.*1., 1e-6);
+    let t1 = Triangle::new((0., 0.).into(), (4., 0.).into(), (0., 4.).into());
+    let t2 = Triangle::new((0., 0.).into(), (0., 4.).into(), (4., 0.).into());
+    let a1 = buffer_triangle(&t1, 0.5).unsigned_area();
+    let a2 = buffer_triangle(&t2, 0.5).unsigned_area();
+    assert!(a1 > 8. && f64::abs(a1 - a2) < 1e-6);
+    assert!(buffer_triangle(&t2, -0.5).unsigned_area() < 8.);
+}
+
+#[test]
+fn geometry_dispatch(){
+    let ls = LineString::from(vec![(0., 0.), (4., 0.)]);
+    // Line strings get round end caps by default.
+    assert_eq!(buffer_geometry(&Geometry::LineString(ls.clone()), 1.), buffer_line_string(&ls, 1., EndCapStyle::Round));
+    assert_area(&buffer_geometry(&Geometry::LineString(ls.clone()), 1.), 8. + std::f64::consts::PI, 0.1);
+    assert_area(&buffer_geometry(&Geometry::Line(Line::new((0., 0.), (4., 0.))), 1.), 8. + std::f64::consts::PI, 0.1);
+    assert_area(&buffer_geometry(&Geometry::Rect(Rect::new((0., 0.), (4., 2.))), -0.5), 3., 1e-6);
+    assert_area(&buffer_geometry(&Geometry::MultiPoint(MultiPoint::from(vec![(0., 0.), (5., 5.)])), 1.), 8., 1e-6);
+}
+
+#[test]
+fn geometry_collection_merged(){
+    let p1 = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let p2 = Polygon::new(LineString::from(vec![(10., 0.), (12., 0.), (12., 2.), (10., 2.)]), vec![]);
+    let inner = GeometryCollection::new_from(vec![Geometry::Polygon(p2)]);
+    let gc = GeometryCollection::new_from(vec![
+        Geometry::Polygon(p1),
+        Geometry::LineString(LineString::from(vec![(2., 1.), (10., 1.)])),
+        Geometry::GeometryCollection(inner),
+        Geometry::Point(Point::new(20., 20.)),
+    ]);
+    let res = buffer_geometry_collection(&gc, 0.5);
+    assert_eq!(res.0.len(), 2);
+    assert_area(&res, 2.*9. + 7.*1. + 1., 1e-6);
+    let res = buffer_geometry_collection(&gc, -0.5);
+    assert_area(&res, 2., 1e-6);
+}
+
+#[test]
+fn buffer_trait(){
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    assert_eq!(p.buffer(1.), buffer_polygon(&p, 1.));
+    let rounded = BufferOptions::new().join_style(JoinStyle::Round);
+    assert_eq!(p.buffer_with(1., &rounded), buffer_polygon_rounded(&p, 1.));
+    let g = Geometry::Polygon(p.clone());
+    assert_eq!(g.buffer(-0.5), p.buffer(-0.5));
+    assert_area(&Point::new(0., 0.).buffer_with(1., &rounded), std::f64::consts::PI, 1e-2);
+}
+
+#[test]
+fn line_string_round_join(){
+    let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+    let opts = BufferOptions::new().join_style(JoinStyle::Round).end_cap(EndCapStyle::Flat);
+    let res = ls.buffer_with(1., &opts);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 7. + std::f64::consts::PI/4., 1e-2);
+    assert!(!res.contains(&Point::new(2.9, -0.9)));
+}
+
+#[test]
+fn single_sided_line_string(){
+    let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+    let opts = BufferOptions::new().single_sided(true).end_cap(EndCapStyle::Round);
+    let left = buffer_with_options(&ls, 1., &opts);
+    assert_area(&left, 3., 1e-6);
+    assert!(!left.contains(&Point::new(1., -0.5)));
+    let right = buffer_with_options(&ls, -1., &opts);
+    assert_area(&right, 5., 1e-6);
+    assert!(right.contains(&Point::new(2.5, -0.5)));
+
+    // Both sides are convex where the line string turns back on itself.
+    let turnback = LineString::from(vec![(0., 0.), (2., 0.), (1., 0.)]);
+    let opts = opts.join_style(JoinStyle::Round);
+    for d in [1., -1.]{
+        let res = buffer_with_options(&turnback, d, &opts);
+        assert_area(&res, 3. + std::f64::consts::FRAC_PI_2, 1e-2);
+        assert!(res.contains(&Point::new(2.5, 0.)));
+    }
+}
+
+#[test]
+fn arc_resolution(){
+    let p = Point::new(0., 0.);
+    let coarse = BufferOptions::new().join_style(JoinStyle::Round).arc_resolution(ArcResolution::QuadrantSegments(2));
+    let default = BufferOptions::new().join_style(JoinStyle::Round);
+    assert_eq!(p.buffer_with(1., &coarse).0[0].exterior().0.len(), 8 + 1);
+    assert_eq!(p.buffer_with(1., &default).0[0].exterior().0.len(), 64 + 1);
+    let fine = BufferOptions::new().join_style(JoinStyle::Round).arc_resolution(ArcResolution::MaxChordError(1e-3));
+    let small = p.buffer_with(1., &fine);
+    let large = p.buffer_with(100., &fine);
+    assert!(small.0[0].exterior().0.len() < large.0[0].exterior().0.len());
+    assert_area(&large, std::f64::consts::PI*1e4, 2.*std::f64::consts::PI*100.*1e-3);
+}
+
+#[test]
+fn miter_limit(){
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (20., 0.8), (0., 1.6)]), vec![]);
+    let unlimited = p.buffer(1.);
+    assert!(unlimited.bounding_rect().unwrap().max().x > 40.);
+    let limited = p.buffer_with(1., &BufferOptions::new().miter_limit(2.));
+    let rect = limited.bounding_rect().unwrap();
+    assert!(f64::abs(rect.max().x - 22.) < 1e-6);
+    assert!(f64::abs(rect.min().x + 1.) < 1e-6);
+    assert!(limited.contains(&Point::new(21.9, 0.8)));
+    let bevel = p.buffer_with(1., &BufferOptions::new().miter_limit(0.));
+    assert!(bevel.bounding_rect().unwrap().max().x < 21.);
+
+    let ls = LineString::from(vec![(0., 0.), (20., 0.8), (0., 1.6)]);
+    let opts = BufferOptions::new().miter_limit(2.).end_cap(EndCapStyle::Flat);
+    let res = ls.buffer_with(1., &opts);
+    assert!(f64::abs(res.bounding_rect().unwrap().max().x - 22.) < 1e-6);
+
+    // The line string turns back on itself at (10, 0).
+    let ls = LineString::from(vec![(0., 0.), (10., 0.), (5., 0.)]);
+    let res = ls.buffer_with(1., &opts);
+    assert!(f64::abs(res.bounding_rect().unwrap().max().x - 12.) < 1e-6);
+    assert_area(&res, 2.*12., 1e-6);
+    let res = ls.buffer_with(1., &BufferOptions::new().end_cap(EndCapStyle::Flat));
+    assert!(f64::abs(res.bounding_rect().unwrap().max().x - 11.) < 1e-6);
+}
+
+#[test]
+fn bevel_join(){
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let opts = BufferOptions::new().join_style(JoinStyle::Bevel);
+    let res = p.buffer_with(1., &opts);
+    assert_eq!(res.0[0].exterior().0.len(), 9);
+    assert_area(&res, 16. - 4.*0.5, 1e-6);
+    assert!(!res.contains(&Point::new(2.9, 2.9)));
+    assert_eq!(p.buffer_with(-0.5, &opts), p.buffer(-0.5));
+
+    let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]);
+    let res = ls.buffer_with(1., &opts.end_cap(EndCapStyle::Flat));
+    assert_area(&res, 7.5, 1e-6);
+    assert!(res.contains(&Point::new(2.4, -0.4)));
+    assert!(!res.contains(&Point::new(2.6, -0.6)));
+}
+
+#[test]
+fn symmetric_arcs(){
+    let p: Polygon = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let opts = BufferOptions::new().join_style(JoinStyle::Round).arc_resolution(ArcResolution::QuadrantSegments(3));
+    let res = p.buffer_with(1., &opts);
+    let ext = res.0[0].exterior();
+    assert_eq!(ext.0.len(), 4*4 + 1);
+    let chord = |i: usize| ((ext.0[i+1].x - ext.0[i].x).powi(2) + (ext.0[i+1].y - ext.0[i].y).powi(2)).sqrt();
+    let expected = 2.*f64::sin(std::f64::consts::PI/12.);
+    let (mut arcs, mut edges) = (0, 0);
+    for i in 0..ext.0.len()-1{
+        if f64::abs(chord(i) - expected) < 1e-9 {arcs += 1;}
+        else if f64::abs(chord(i) - 2.) < 1e-9 {edges += 1;}
+    }
+    assert_eq!((arcs, edges), (12, 4));
+
+    let tolerance = 1e-2;
+    let opts = BufferOptions::new().join_style(JoinStyle::Round).arc_resolution(ArcResolution::MaxChordError(tolerance));
+    for radius in [0.5, 5., 500.]{
+        let res: MultiPolygon = Point::new(0., 0.).buffer_with(radius, &opts);
+        let ext = res.0[0].exterior();
+        for w in ext.0.windows(2){
+            let mid = ((w[0].x + w[1].x)/2., (w[0].y + w[1].y)/2.);
+            let sagitta = radius - (mid.0*mid.0 + mid.1*mid.1).sqrt();
+            assert!(sagitta <= tolerance + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn buffer_errors(){
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    assert_eq!(try_buffer_polygon(&p, 1.), Ok(buffer_polygon(&p, 1.)));
+    assert!(matches!(try_buffer_polygon(&p, f64::NAN), Err(BufferError::InvalidInput(_))));
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (2., f64::INFINITY), (2., 2.)]), vec![]);
+    assert!(matches!(p.try_buffer(1.), Err(BufferError::InvalidInput(_))));
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (1e308, 0.), (1e308, 1e308), (0., 1e308)]), vec![]);
+    assert_eq!(p.try_buffer(1.).map(|res| res.0.len()), Ok(1));
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]), vec![]);
+    // An overflowing distance is reported or buffered, but never panics.
+    if let Ok(res) = p.try_buffer(f64::MAX) {
+        assert!(res.0.iter().flat_map(|p| p.exterior().0.iter()).all(|c| c.x.is_finite() && c.y.is_finite()));
+    }
+    assert_eq!(MultiPolygon::new(vec![]).try_buffer(1.), Ok(MultiPolygon::new(vec![])));
+
+    let ls = LineString::from(vec![(0., 0.), (f64::NAN, 0.)]);
+    assert!(matches!(try_buffer_with_options(&ls, 1., &BufferOptions::new()), Err(BufferError::InvalidInput(_))));
+    let gc = GeometryCollection::new_from(vec![Geometry::Point(Point::new(0., 0.)), Geometry::Point(Point::new(f64::NAN, 0.))]);
+    assert!(gc.try_buffer(1.).is_err());
+
+    // A member whose hole crosses its shell is reported instead of panicking in `geo` where the members are merged.
+    let shell = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]);
+    let hole = LineString::from(vec![(3., 1.), (3., 3.), (5., 3.), (5., 1.)]);
+    let mp = MultiPolygon::new(vec![
+        Polygon::new(shell, vec![hole]),
+        Polygon::new(LineString::from(vec![(2., 2.), (6., 2.), (6., 6.), (2., 6.)]), vec![]),
+    ]);
+    assert!(matches!(try_buffer_multi_polygon(&mp, -0.5), Err(BufferError::InvalidInput(_))));
+    assert!(matches!(try_buffer_multi_polygon(&mp, 0.5), Err(BufferError::InvalidInput(_))));
+}
+
+#[test]
+fn validation(){
+    let kinds = |r: Result<(), Vec<Invalidity>>| r.unwrap_err().iter().map(|e| e.kind).collect::<Vec<_>>();
+    let shell = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]);
+    let hole = LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.)]);
+    assert!(validate_for_buffer(&Polygon::new(shell.clone(), vec![hole.clone()])).is_ok());
+
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 0.), (4., 4.), (0., 4.)]), vec![]);
+    let res = validate_for_buffer(&p).unwrap_err();
+    assert_eq!(res, vec![Invalidity{kind: InvalidityKind::RepeatedPoint, location: Coordinate(4., 0.)}]);
+
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (0., 0.)]), vec![]);
+    assert!(kinds(validate_for_buffer(&p)).contains(&InvalidityKind::TooFewVertices));
+
+    let outside = LineString::from(vec![(5., 5.), (5., 6.), (6., 6.), (6., 5.)]);
+    let res = validate_for_buffer(&Polygon::new(shell.clone(), vec![outside])).unwrap_err();
+    assert_eq!(res, vec![Invalidity{kind: InvalidityKind::HoleOutsideShell, location: Coordinate(5., 5.)}]);
+
+    let crossing = LineString::from(vec![(3., 1.), (3., 2.), (5., 2.), (5., 1.)]);
+    let res = kinds(validate_for_buffer(&Polygon::new(shell.clone(), vec![crossing])));
+    assert!(res.contains(&InvalidityKind::SelfIntersection) && res.contains(&InvalidityKind::HoleOutsideShell));
+
+    let large = LineString::from(vec![(0.5, 0.5), (0.5, 3.5), (3.5, 3.5), (3.5, 0.5)]);
+    let res = validate_for_buffer(&Polygon::new(shell.clone(), vec![large, hole.clone()])).unwrap_err();
+    assert_eq!(res, vec![Invalidity{kind: InvalidityKind::NestedHoles, location: Coordinate(1., 1.)}]);
+
+    let p1 = Polygon::new(shell.clone(), vec![]);
+    let p2 = Polygon::new(LineString::from(vec![(3., 3.), (6., 3.), (6., 6.), (3., 6.)]), vec![]);
+    let p3 = Polygon::new(LineString::from(vec![(4., 0.), (6., 0.), (6., 2.), (4., 2.)]), vec![]);
+    let res = validate_for_buffer(&MultiPolygon::new(vec![p1.clone(), p2])).unwrap_err();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].kind, InvalidityKind::OverlappingMembers);
+    assert!(res[0].location.0 > 3. && res[0].location.0 < 4. && res[0].location.1 > 3. && res[0].location.1 < 4.);
+    assert!(validate_for_buffer(&MultiPolygon::new(vec![p1, p3])).is_ok());
+}
+
+#[test]
+fn input_cleaning(){
+    let expected = buffer_polygon(&Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]), vec![]), 1.);
+    let dirty = Polygon::new(LineString::from(vec![(0., 0.), (0., 0.), (2., 0.), (4., 0.), (4., 0.), (4., 2.), (4., 4.), (0., 4.), (0., 2.), (0., 0.)]), vec![
+        LineString::from(vec![(1., 1.), (1., 1.), (2., 1.)]),
+        LineString::from(vec![(1., 1.), (2., 1.), (3., 1.)]),
+    ]);
+    let res = dirty.try_buffer(1.).unwrap();
+    assert_eq!(res.0.len(), 1);
+    assert_eq!(res.0[0].exterior().0.len(), 5);
+    assert_eq!(res.0[0].interiors().len(), 0);
+    assert_area(&res, expected.unsigned_area(), 1e-9);
+
+    let spike = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (2., 4.), (2., 6.), (2., 4.), (0., 4.)]), vec![]);
+    assert_area(&spike.try_buffer(-1.).unwrap(), 4., 1e-9);
+    let hole = LineString::from(vec![(1., 1.), (1., 3.), (1., 3.), (3., 3.), (3., 2.), (3., 1.)]);
+    let res = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]), vec![hole]).try_buffer(-0.25).unwrap();
+    assert_area(&res, 3.5*3.5 - 2.5*2.5, 1e-9);
+
+    let degenerate = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (4., 0.)]), vec![]);
+    assert_eq!(degenerate.try_buffer(1.), Ok(MultiPolygon::new(vec![])));
+    assert!(skeleton_of_polygon_to_linestring(&degenerate, true).is_empty());
+}
+
+#[test]
+fn ring_orientation(){
+    use geo::{Winding, winding_order::WindingOrder};
+    let ccw = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]), vec![LineString::from(vec![(1., 1.), (1., 3.), (3., 3.), (3., 1.)])]);
+    let cw = Polygon::new(LineString::from(vec![(0., 0.), (0., 4.), (4., 4.), (4., 0.)]), vec![LineString::from(vec![(1., 1.), (3., 1.), (3., 3.), (1., 3.)])]);
+    assert_eq!(cw.buffer(0.5), ccw.buffer(0.5));
+    assert_eq!(cw.buffer(-0.2), ccw.buffer(-0.2));
+    assert_area(&cw.buffer(-0.2), 3.6*3.6 - 2.4*2.4, 1e-9);
+    let is_normalized = |mp: &MultiPolygon| mp.0.iter().all(|p| {
+        p.exterior().winding_order() == Some(WindingOrder::CounterClockwise)
+            && p.interiors().iter().all(|i| i.winding_order() == Some(WindingOrder::Clockwise))
+    });
+    assert!(is_normalized(&cw.buffer_with(0.5, &BufferOptions::new().join_style(JoinStyle::Round))));
+    let ring = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+    let res = ring.buffer(0.5);
+    assert_eq!(res.0[0].interiors().len(), 1);
+    assert!(is_normalized(&res));
+    assert!(is_normalized(&MultiPoint::from(vec![(0., 0.), (1., 0.)]).buffer(1.)));
+}
+
+#[test]
+fn nested_islands(){
+    let square = |a: f64, b: f64| LineString::from(vec![(a, a), (b, a), (b, b), (a, b)]);
+    let lake = Polygon::new(square(0., 10.), vec![square(2., 8.)]);
+    let island = Polygon::new(square(3., 7.), vec![square(4., 6.)]);
+    let mp = MultiPolygon::new(vec![lake.clone(), island.clone()]);
+    for mp in [mp, MultiPolygon::new(vec![island.clone(), lake.clone()])]{
+        let res = mp.buffer(0.25);
+        assert_eq!(res.0.len(), 2);
+        assert!(res.0.iter().all(|p| p.interiors().len() == 1));
+        assert_area(&res, 10.5*10.5 - 5.5*5.5 + 4.5*4.5 - 1.5*1.5, 1e-9);
+        assert!(!res.contains(&Point::new(5., 5.)));
+        assert!(!res.contains(&Point::new(2.5, 5.)));
+        let res = mp.buffer(-0.25);
+        assert_eq!(res.0.len(), 2);
+        assert!(res.0.iter().all(|p| p.interiors().len() == 1));
+        assert_area(&res, 9.5*9.5 - 6.5*6.5 + 3.5*3.5 - 2.5*2.5, 1e-9);
+        assert!(!res.contains(&Point::new(5., 5.)));
+    }
+    // The first member vanishes, which reorders the remaining rings of the wavefront.
+    let mp = MultiPolygon::new(vec![Polygon::new(square(20., 20.2), vec![]), lake, island]);
+    let res = mp.buffer(-0.25);
+    assert_eq!(res.0.len(), 2);
+    assert!(res.0.iter().all(|p| p.interiors().len() == 1));
+    assert_area(&res, 9.5*9.5 - 6.5*6.5 + 3.5*3.5 - 2.5*2.5, 1e-9);
+}
+
+#[test]
+fn overlapping_members(){
+    let square = |x: f64, y: f64, w: f64| Polygon::new(LineString::from(vec![(x, y), (x+w, y), (x+w, y+w), (x, y+w)]), vec![]);
+    let mp = MultiPolygon::new(vec![square(0., 0., 4.), square(2., 2., 4.), square(10., 10., 1.)]);
+    let union = MultiPolygon::new(vec![
+        Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (6., 2.), (6., 6.), (2., 6.), (2., 4.), (0., 4.)]), vec![]),
+        square(10., 10., 1.),
+    ]);
+    for d in [0.5, -0.5, -1.5]{
+        assert_area(&mp.buffer(d), union.buffer(d).unsigned_area(), 1e-9);
+    }
+    assert_eq!(mp.buffer(0.5).0.len(), 2);
+
+    let p = square(0., 0., 4.);
+    let duplicated = MultiPolygon::new(vec![p.clone(), p.clone(), square(1., 1., 1.)]);
+    assert_area(&duplicated.buffer(1.), 36., 1e-9);
+    assert_area(&duplicated.buffer(-1.), 4., 1e-9);
+    // A member inside another one whose vertices all lie on the boundary of the other one.
+    let inscribed = MultiPolygon::new(vec![p.clone(), Polygon::new(LineString::from(vec![(2., 0.), (4., 2.), (2., 4.), (0., 2.)]), vec![])]);
+    assert_eq!(inscribed.buffer(-1.).0.len(), 1);
+    assert_area(&inscribed.buffer(-1.), 4., 1e-9);
+}
+
+#[test]
+fn touching_members(){
+    use geo::BooleanOps;
+    let square = |x: f64, y: f64, w: f64| Polygon::new(LineString::from(vec![(x, y), (x+w, y), (x+w, y+w), (x, y+w)]), vec![]);
+    let by_vertex = MultiPolygon::new(vec![square(0., 0., 2.), square(2., 2., 2.)]);
+    let res = by_vertex.buffer(0.5);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 9. + 9. - 1., 1e-5);
+    let res = by_vertex.buffer(-0.5);
+    assert_eq!(res.0.len(), 2);
+    assert_area(&res, 2., 1e-5);
+    // The touching vertices are moved by a few times the tolerance, also with a coarse one.
+    let (_, report) = by_vertex.try_buffer_with_report(0.5, &BufferOptions::new()).unwrap();
+    assert!(report.separation.is_some_and(|x| x > 0. && x < 1e-7));
+    let (res, report) = by_vertex.try_buffer_with_report(0.5, &BufferOptions::new().tolerance(1e-3)).unwrap();
+    assert!(report.separation.is_some_and(|x| x <= 4e-3 + 1e-12));
+    assert_area(&res, 9. + 9. - 1., 1e-2);
+    assert_eq!(square(0., 0., 2.).try_buffer_with_report(0.5, &BufferOptions::new()).unwrap().1.separation, None);
+    // Members which come close to each other without touching are not moved.
+    let close = MultiPolygon::new(vec![square(0., 0., 2.), square(2. + 1e-10, 2. + 1e-10, 2.)]);
+    let (res, report) = close.try_buffer_with_report(0.5, &BufferOptions::new()).unwrap();
+    assert_eq!(report.separation, None);
+    assert_area(&res, 9. + 9. - 1., 1e-5);
+
+    let by_edge = MultiPolygon::new(vec![square(0., 0., 2.), square(2., 0., 2.)]);
+    let res = by_edge.buffer(-0.5);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 3., 1e-9);
+    let by_partial_edge = MultiPolygon::new(vec![square(0., 0., 2.), square(2., 1., 2.)]);
+    assert_area(&by_partial_edge.buffer(0.5), 8. + 14.*0.5 + 4.*0.5*0.5, 1e-5);
+
+    let shell = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)]);
+    let diamond = LineString::from(vec![(0., 2.), (1., 1.), (2., 2.), (1., 3.)]);
+    let p = Polygon::new(shell.clone(), vec![diamond.clone()]);
+    let res = p.buffer(0.25);
+    assert_eq!(res.0.len(), 1);
+    assert_eq!(res.0[0].interiors().len(), 1);
+    let h = 1. - 0.25*std::f64::consts::SQRT_2;
+    assert_area(&res, 4.5*4.5 - 2.*h*h, 1e-5);
+    let res = p.buffer(-0.25);
+    let hole = Polygon::new(diamond, vec![]).buffer(0.25);
+    let expected = square(0.25, 0.25, 3.5).difference(&hole);
+    assert_eq!(res.0.len(), expected.0.len());
+    assert_area(&res, expected.unsigned_area(), 1e-5);
+    assert!(validate_for_buffer(&res).is_ok());
+
+    // The same shape whose exterior ring touches itself instead of having a hole.
+    let pinched = Polygon::new(LineString::from(vec![(0., 0.), (0., 2.), (1., 1.), (2., 2.), (1., 3.), (0., 2.), (0., 4.), (4., 4.), (4., 0.)]), vec![]);
+    assert_area(&pinched.buffer(-0.25), expected.unsigned_area(), 1e-5);
+    assert_area(&pinched.buffer(0.25), 4.5*4.5 - 2.*h*h, 1e-5);
+
+    let two_holes = Polygon::new(shell, vec![
+        LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.)]),
+        LineString::from(vec![(2., 2.), (2., 3.), (3., 3.), (3., 2.)]),
+    ]);
+    let res = two_holes.buffer(-0.25);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 3.5*3.5 - 2.*1.5*1.5 + 0.5*0.5, 1e-5);
+    assert!(validate_for_buffer(&res).is_ok());
+    assert_area(&two_holes.buffer(0.25), 4.5*4.5 - 2.*0.5*0.5, 1e-5);
+}
+
+#[test]
+fn simultaneous_events(){
+    // All the reflex and convex vertices of a symmetric star meet at its center at once.
+    let star = Polygon::new(LineString::from(vec![
+        (7., 0.), (3., 3.), (0., 7.), (-3., 3.), (-7., 0.), (-3., -3.), (0., -7.), (3., -3.),
+    ]), vec![]);
+    let skel = skeleton_of_polygon_to_linestring(&star, true);
+    assert_eq!(skel.len(), 8);
+    for ls in &skel{
+        let end = ls.0[ls.0.len()-1];
+        assert!(f64::hypot(end.x, end.y) < 1e-9, "the skeleton ends at ({}, {})", end.x, end.y);
+    }
+
+    let staircase = Polygon::new(LineString::from(vec![(0., 0.), (3., 0.), (3., 1.), (2., 1.), (2., 2.), (1., 2.), (1., 3.), (0., 3.)]), vec![]);
+    assert_area(&staircase.buffer(-0.25), 3.25, 1e-9);
+    let res = staircase.buffer(-0.4);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 1.84, 1e-9);
+    // The steps vanish at once, leaving a square.
+    let res = staircase.buffer(-0.5);
+    assert_eq!(res.0.len(), 1);
+    assert_eq!(res.0[0].exterior().0.len(), 5);
+    assert_area(&res, 1., 1e-9);
+    assert!(staircase.buffer(-1.).0.is_empty());
+
+    // The four reflex vertices of a cross meet at its center together with the ends of its arms.
+    let cross = Polygon::new(LineString::from(vec![
+        (1., 0.), (3., 0.), (3., 1.), (4., 1.), (4., 3.), (3., 3.), (3., 4.), (1., 4.), (1., 3.), (0., 3.), (0., 1.), (1., 1.),
+    ]), vec![]);
+    let res = cross.buffer(-0.5);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 5., 1e-9);
+    assert!(cross.buffer(-1.).0.is_empty());
+    assert_area(&cross.buffer(0.5), 12. + 16.*0.5 + (8. - 4.)*0.25, 1e-9);
+}
+
+#[test]
+fn perturbation_fallback(){
+    let p = Polygon::new(LineString::from(vec![
+        (2., 0.), (2.5, 1.), (1.5, 1.5), (0.5, 2.5), (0., 1.), (-1., 1.5), (-2., 1.5), (-3.5, 0.5),
+        (-1., -0.5), (-3., -2.5), (-1., -2.5), (0., -4.), (0.5, -1.5), (1.5, -1.), (1.5, -0.5),
+    ]), vec![]);
+    let (res, report) = p.try_buffer_with_report(-0.7, &BufferOptions::new()).unwrap();
+    assert_eq!(report, BufferReport::default());
+    assert_eq!(res, p.buffer(-0.7));
+
+    // With this coarse tolerance, the floating-point skeleton merges events which are not simultaneous into an inconsistent one,
+    // thus it is constructed from the perturbed input.
+    let (res, report) = p.try_buffer_with_report(-0.7, &BufferOptions::new().tolerance(1e-2)).unwrap();
+    assert_eq!(report.fallback_used(), !cfg!(feature = "exact"));
+    assert!(report.perturbation.unwrap_or(0.) < 0.2);
+    assert!(validate_for_buffer(&res).is_ok());
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, p.buffer(-0.7).unsigned_area(), 0.1);
+
+    // The union of two overlapping stars. The floating-point skeleton of it is consistent, but the offset assembled from it
+    // backtracks and crosses itself, thus it is constructed from the perturbed input as well.
+    let stars = Polygon::new(LineString::from(vec![
+        (-10.133952535654663, 9.862221261869308e-9), (-4.230833090807556, 1.752468446818627), (-7.856364138390182, 7.856364140836037),
+        (-2.0846066549078213, 5.032685617533959), (-7.41636618784014e-9, 10.563901166049279), (1.745634458993317, 4.214334378567971),
+        (3.8205264136537327, 5.461914340583123), (1.674268208478333, 9.325731793967522), (6.163963250850083, 6.870963464108742),
+        (7.93711414191568, 7.937114129391945), (7.184460453723313, 6.313000361052788), (7.979291670058609, 5.878421495286263),
+        (9.999999992583634, 11.167442480651177), (11.943893484805466, 5.692974011031426), (18.057915531133055, 9.057915548411644),
+        (15.108064405654313, 3.115829567042626), (20.418433807824492, 1.0000000098622213), (14.124716900323273, -0.7085136970603099),
+        (18.19662963602388, -7.196629633578025), (11.894559107755066, -3.573870321186744), (9.999999992583634, -9.647592922123634),
+        (7.9732430503114475, -3.893024136694633), (5.939477883313538, -4.884056200655662), (7.261698357795121, -7.2616983553492656),
+        (4.260521315072419, -5.702193786772453), (2.3828246221288456, -6.617175390394889), (3.137485139106156, -5.11862938609954),
+        (1.841194443438889, -4.445036610039436), (-7.41636618784014e-9, -10.25159778204795), (-2.2598747387186275, -5.455820252569877),
+        (-7.085919149185775, -7.08591916170951), (-4.094991005922912, -1.6962008079611888),
+    ]), vec![LineString::from(vec![(4.943417899344803, -1.5325490733706584), (4.188152693246247, -1.7347896596514811), (4.531050764058472, -2.3513955673300853)])]);
+    for (distance, count) in [(-2.8, 6), (-3., 4), (-3.2, 2)]{
+        let (res, report) = stars.try_buffer_with_report(distance, &BufferOptions::new()).unwrap();
+        assert_eq!(report.fallback_used(), !cfg!(feature = "exact"));
+        assert!(validate_for_buffer(&res).is_ok(), "the buffer by {distance} is invalid");
+        assert_eq!(res.0.len(), count);
+    }
+}
+
+#[test]
+fn straight_skeleton_graph(){
+    let square = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let skel = StraightSkeleton::of_polygon(&square, true).unwrap();
+    assert_eq!(skel.nodes().len(), 5);
+    assert_eq!(skel.nodes()[4].kind(), SkeletonNodeKind::Root);
+    assert_eq!(skel.nodes()[4].time(), 1.);
+    for (i, arc) in skel.arcs().iter().enumerate(){
+        let vertex = InputIndex{polygon: 0, ring: 0, index: i};
+        assert_eq!((arc.start(), arc.end(), arc.input_vertex()), (i, Some(4), Some(vertex)));
+        assert_eq!((arc.left_edge().index, arc.right_edge()), ((i+3)%4, vertex));
+    }
+    let skel = StraightSkeleton::of_polygon(&square, false).unwrap();
+    assert_eq!(skel.arcs().len(), 4);
+    assert!(skel.arcs().iter().all(|arc| arc.end().is_none()));
+
+    // The reflex vertex at the bottom of the notch reaches the bottom edge, where the wavefront splits.
+    let notched = Polygon::new(LineString::from(vec![(0., 0.), (10., 0.), (10., 4.), (5.5, 4.), (5., 1.), (4.5, 4.), (0., 4.)]), vec![]);
+    let skel = StraightSkeleton::of_polygon(&notched, true).unwrap();
+    let (nodes, arcs) = (skel.nodes(), skel.arcs());
+    for (i, node) in nodes.iter().enumerate(){
+        let starting = arcs.iter().filter(|arc| arc.start() == i).count();
+        match node.kind(){
+            SkeletonNodeKind::Tree => assert_eq!(starting, 1),
+            SkeletonNodeKind::Split => assert!(starting >= 2),
+            SkeletonNodeKind::Root => assert_eq!(starting, 0),
+        }
+        assert_eq!(node.time() == 0., i < 7);
+    }
+    assert!(nodes.iter().any(|node| node.kind() == SkeletonNodeKind::Split));
+    for arc in arcs{
+        let (start, end) = (nodes[arc.start()], nodes[arc.end().unwrap()]);
+        let t = end.time() - start.time();
+        assert!(t >= 0.);
+        if arc.velocity() == (geo_types::Coord{x: 0., y: 0.}) {
+            // The arc where the opposite edges on the ridge collapse at once.
+            assert!(t < 1e-9);
+            continue;
+        }
+        let (x, y) = (start.location().x + arc.velocity().x*t, start.location().y + arc.velocity().y*t);
+        assert!(f64::hypot(x - end.location().x, y - end.location().y) < 1e-9);
+    }
+}
+
+#[test]
+fn skeleton_faces(){
+    let square = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let faces = StraightSkeleton::of_polygon(&square, true).unwrap().faces().unwrap();
+    assert_eq!(faces.len(), 4);
+    for (i, (edge, face)) in faces.iter().enumerate(){
+        assert_eq!(edge.index, i);
+        assert_eq!(face.exterior().0.len(), 4);
+        assert_eq!(face.signed_area(), 1.);
+    }
+    let faces = StraightSkeleton::of_polygon(&square, false).unwrap().faces().unwrap();
+    assert_eq!(faces.len(), 4);
+    assert!(faces.iter().all(|(_, face)| face.signed_area() > 0.));
+
+    // The faces partition the polygon, including the faces of the edges split by the reflex vertex and of the hole.
+    let notched = Polygon::new(
+        LineString::from(vec![(0., 0.), (10., 0.), (10., 4.), (5.5, 4.), (5., 1.), (4.5, 4.), (0., 4.)]),
+        vec![LineString::from(vec![(7., 1.), (7., 2.), (9., 2.), (9., 1.)])],
+    );
+    let faces = StraightSkeleton::of_polygon(&notched, true).unwrap().faces().unwrap();
+    assert_eq!(faces.len(), 11);
+    assert!(faces.iter().all(|(_, face)| face.signed_area() > 0.));
+    let total = faces.iter().map(|(_, face)| face.signed_area()).sum::<f64>();
+    assert!(f64::abs(total - notched.unsigned_area()) < 1e-9);
+}
+
+#[test]
+fn roof_mesh(){
+    let area_3d = |roof: &RoofMesh| roof.triangles().iter().map(|t| {
+        let [a, b, c] = t.map(|i| roof.vertices()[i]);
+        let (u, v) = ([b[0]-a[0], b[1]-a[1], b[2]-a[2]], [c[0]-a[0], c[1]-a[1], c[2]-a[2]]);
+        let n = [u[1]*v[2] - u[2]*v[1], u[2]*v[0] - u[0]*v[2], u[0]*v[1] - u[1]*v[0]];
+        assert!(n[2] >= 0.);
+        f64::sqrt(n[0]*n[0] + n[1]*n[1] + n[2]*n[2])/2.
+    }).sum::<f64>();
+    let rect = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![]);
+    let pitch = std::f64::consts::FRAC_PI_4;
+    let roof = RoofMesh::of_polygon(&rect, &RoofOptions::new().pitch(Pitch::Uniform(pitch))).unwrap();
+    assert_eq!((roof.vertices().len(), roof.faces().len(), roof.triangles().len()), (6, 4, 6));
+    assert!(f64::abs(area_3d(&roof) - 8./pitch.cos()) < 1e-9);
+    let obj = String::from_utf8(roof.to_obj()).unwrap();
+    assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 6);
+    assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 6);
+    let stl = roof.to_stl();
+    assert_eq!(stl.len(), 84 + 50*6);
+    assert_eq!(u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]), 6);
+
+    // A courtyard building: every plane has the same pitch, thus the area of the roof is the area of the footprint over its cosine.
+    let courtyard = Polygon::new(
+        LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)]),
+        vec![LineString::from(vec![(3., 3.), (3., 7.), (7., 7.), (7., 3.)])],
+    );
+    let pitch = std::f64::consts::FRAC_PI_6;
+    let roof = RoofMesh::of_polygon(&courtyard, &RoofOptions::new()).unwrap();
+    assert_eq!(roof.faces().len(), 8);
+    assert!(f64::abs(area_3d(&roof) - 84./pitch.cos()) < 1e-9);
+
+    // The pitches follow the edges of the footprint, including the collinear vertex which is removed before building the skeleton.
+    // The front and back planes of different pitches meet at the ridge between the gables.
+    let rect = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![]);
+    let opts = RoofOptions::new().pitch(Pitch::PerEdge(vec![0.5, 0.5, 0., 0.3, 0.])).gable(2, Gable::Full).gable(4, Gable::Full);
+    let roof = RoofMesh::of_polygon(&rect, &opts).unwrap();
+    assert_eq!(roof.faces().len(), 4);
+    let ridge = 2.*f64::tan(0.5)*f64::tan(0.3)/(f64::tan(0.5) + f64::tan(0.3));
+    for (_, face) in roof.faces(){
+        let height = face.iter().map(|&i| roof.vertices()[i][2]).fold(0., f64::max);
+        assert!(f64::abs(height - ridge) < 1e-9);
+    }
+    // Hip planes of different pitches would leave cracks along the hips.
+    let res = RoofMesh::of_polygon(&rect, &RoofOptions::new().pitch(Pitch::PerEdge(vec![0.5, 0.5, 0.2, 0.5, 0.2])));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+    // The collinear edges are merged into one roof plane, thus they cannot have different pitches.
+    let res = RoofMesh::of_polygon(&rect, &RoofOptions::new().pitch(Pitch::PerEdge(vec![0.5, 0.4, 0.2, 0.5, 0.2])));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+    let res = RoofMesh::of_polygon(&rect, &RoofOptions::new().pitch(Pitch::PerEdge(vec![0.5; 4])));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+    let res = RoofMesh::of_polygon(&rect, &RoofOptions::new().pitch(Pitch::Uniform(std::f64::consts::FRAC_PI_2)));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+}
+
+#[test]
+fn roof_gables(){
+    // The areas of the roof, and of its projection to the ground to which the walls do not contribute.
+    let areas = |roof: &RoofMesh| roof.triangles().iter().fold((0., 0.), |(area, projected), t| {
+        let [a, b, c] = t.map(|i| roof.vertices()[i]);
+        let (u, v) = ([b[0]-a[0], b[1]-a[1], b[2]-a[2]], [c[0]-a[0], c[1]-a[1], c[2]-a[2]]);
+        let n = [u[1]*v[2] - u[2]*v[1], u[2]*v[0] - u[0]*v[2], u[0]*v[1] - u[1]*v[0]];
+        assert!(n[2] >= 0.);
+        (area + f64::sqrt(n[0]*n[0] + n[1]*n[1] + n[2]*n[2])/2., projected + n[2]/2.)
+    });
+    let rect = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![]);
+    let opts = RoofOptions::new().pitch(Pitch::Uniform(std::f64::consts::FRAC_PI_4));
+    let roof = RoofMesh::of_polygon(&rect, &opts.clone().gable(1, Gable::Full).gable(3, Gable::Full)).unwrap();
+    assert_eq!((roof.vertices().len(), roof.faces().len()), (6, 4));
+    let ridge = roof.vertices().iter().filter(|v| v[2] > 0.).collect::<Vec<_>>();
+    assert_eq!(ridge.len(), 2);
+    for x in [0., 4.]{
+        assert!(ridge.iter().any(|v| f64::hypot(v[0] - x, v[1] - 1.) < 1e-12 && f64::abs(v[2] - 1.) < 1e-12));
+    }
+    let (area, projected) = areas(&roof);
+    assert!(f64::abs(area - (8.*f64::sqrt(2.) + 2.)) < 1e-9);
+    assert!(f64::abs(projected - 8.) < 1e-9);
+
+    // The hip planes reach the half of their depth, and the walls of the Dutch gables stand on them.
+    let roof = RoofMesh::of_polygon(&rect, &opts.clone().gable(1, Gable::Dutch(0.5)).gable(3, Gable::Dutch(0.5))).unwrap();
+    assert_eq!((roof.vertices().len(), roof.faces().len()), (10, 6));
+    assert!(roof.vertices().iter().any(|v| f64::hypot(v[0] - 3.5, v[1] - 0.5) < 1e-12 && f64::abs(v[2] - 0.5) < 1e-12));
+    assert!(roof.vertices().iter().any(|v| f64::hypot(v[0] - 3.5, v[1] - 1.) < 1e-12 && f64::abs(v[2] - 1.) < 1e-12));
+    let (area, projected) = areas(&roof);
+    assert!(f64::abs(area - (8.*f64::sqrt(2.) + 0.5)) < 1e-9);
+    assert!(f64::abs(projected - 8.) < 1e-9);
+
+    // Both wings of an L-shaped building end with gables.
+    let l_shape = Polygon::new(LineString::from(vec![(0., 0.), (6., 0.), (6., 2.), (2., 2.), (2., 6.), (0., 6.)]), vec![]);
+    let roof = RoofMesh::of_polygon(&l_shape, &opts.clone().gable(1, Gable::Full).gable(4, Gable::Full)).unwrap();
+    let (area, projected) = areas(&roof);
+    assert!(f64::abs(area - (20.*f64::sqrt(2.) + 2.)) < 1e-9);
+    assert!(f64::abs(projected - 20.) < 1e-9);
+
+    let res = RoofMesh::of_polygon(&rect, &opts.clone().gable(4, Gable::Full));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+    let res = RoofMesh::of_polygon(&rect, &opts.gable(1, Gable::Dutch(1.)));
+    assert!(matches!(res, Err(BufferError::InvalidInput(_))));
+}
+
+#[test]
+fn ray_predicates(){
+    let r1 = Ray::new((0., 0.).into(), (1., 1.).into());
+    // The rays meet at (1e-12, 1e-12) and (-1e-12, -1e-12) respectively, i.e. on the second ray but before the first ray.
+    assert!(r1.is_intersect(&Ray::new((1e-12, 0.).into(), (1e-12, 1.).into())));
+    assert!(!r1.is_intersect(&Ray::new((-1e-12, 0.).into(), (-1e-12, 1.).into())));
+    assert!(r1.is_intersect(&Ray::new((500000.3, 500000.3).into(), (500001.3, 500000.3).into())));
+    assert!(!r1.is_intersect(&Ray::new((500000.3, 500000.3 - 1e-9).into(), (500001.3, 500000.3 - 1e-9).into())));
+}
+
+#[test]
+fn coordinate_scales(){
+    let shape = |ox: f64, oy: f64, s: f64| {
+        let exterior = [(0., 0.), (10., 0.), (10., 6.), (6., 6.), (6., 3.), (4., 3.), (4., 6.), (0., 6.)];
+        let hole = [(1., 1.), (1., 2.), (3., 2.), (3., 1.)];
+        let ring = |crds: &[(f64, f64)]| LineString::from(crds.iter().map(|(x, y)| (ox + x*s, oy + y*s)).collect::<Vec<_>>());
+        Polygon::new(ring(&exterior), vec![ring(&hole)])
+    };
+    let base = shape(0., 0., 1.);
+    // UTM-like coordinates, EPSG:3857-like coordinates and millimetre-sized features.
+    for (ox, oy, s) in [(500000.3, 4100000.7, 1.), (14000000.37, 4500000.81, 20.), (0., 0., 1e-4)]{
+        let p = shape(ox, oy, s);
+        for d in [-1.5, -0.7, -0.3, 0.4, 1.3]{
+            let expected = base.buffer(d);
+            let res = p.buffer(d*s);
+            assert_eq!(res.0.len(), expected.0.len());
+            assert_area(&res, expected.unsigned_area()*s*s, 1e-9*expected.unsigned_area()*s*s);
+        }
+        // Line strings and points are buffered in the same range.
+        let ls = LineString::from(vec![(ox, oy), (ox + 4.*s, oy), (ox + 4.*s, oy + 3.*s), (ox + 1.*s, oy + 3.*s)]);
+        let expected = LineString::from(vec![(0., 0.), (4., 0.), (4., 3.), (1., 3.)]).buffer(0.4);
+        assert_area(&ls.buffer(0.4*s), expected.unsigned_area()*s*s, 1e-9*expected.unsigned_area()*s*s);
+        let res = Geometry::Point(Point::new(ox, oy)).buffer(0.4*s);
+        assert_area(&res, Point::new(0., 0.).buffer(0.4).unsigned_area()*s*s, 1e-9*s*s);
+    }
+}
+
+#[test]
+fn skeleton_coordinate_scales(){
+    let shape = |ox: f64, oy: f64, s: f64| Polygon::new(LineString::from(
+        [(0., 0.), (12., 0.), (13., 7.), (6., 11.), (-1., 6.)].iter().map(|(x, y)| (ox + x*s, oy + y*s)).collect::<Vec<_>>()
+    ), vec![]);
+    let expected = StraightSkeleton::of_polygon(&shape(0., 0., 1.), true).unwrap();
+    assert_eq!((expected.nodes().len(), expected.arcs().len()), (8, 7));
+    let expected_roof = RoofMesh::of_polygon(&shape(0., 0., 1.), &RoofOptions::new()).unwrap();
+    // EPSG:3857 coordinates in metres, with features from a millimetre to a few metres.
+    let (ox, oy) = (-8238310., 4970071.);
+    for s in [1e-4, 1e-2, 1.]{
+        let skel = StraightSkeleton::of_polygon(&shape(ox, oy, s), true).unwrap();
+        assert_eq!((skel.nodes().len(), skel.arcs().len()), (8, 7));
+        for (n, e) in skel.nodes().iter().zip(expected.nodes()){
+            assert!(f64::abs(n.location().x - ox - e.location().x*s) < 1e-7 && f64::abs(n.location().y - oy - e.location().y*s) < 1e-7);
+            assert!(f64::abs(n.time() - e.time()*s) < 1e-7);
+            assert_eq!(n.kind(), e.kind());
+        }
+        let roof = RoofMesh::of_polygon(&shape(ox, oy, s), &RoofOptions::new()).unwrap();
+        assert_eq!(roof.vertices().len(), expected_roof.vertices().len());
+        assert_eq!(roof.triangles(), expected_roof.triangles());
+        for (v, e) in roof.vertices().iter().zip(expected_roof.vertices()){
+            assert!(f64::abs(v[0] - ox - e[0]*s) < 1e-7 && f64::abs(v[1] - oy - e[1]*s) < 1e-7 && f64::abs(v[2] - e[2]*s) < 1e-7);
+        }
+    }
+}
+
+#[test]
+fn tolerance(){
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (1., 0.), (1.000001, 0.0000005), (1., 1.), (0., 1.)]), vec![]);
+    // The tiny bump at (1, 0) is kept unless the tolerance exceeds its size.
+    let corner = |res: &MultiPolygon| res.0[0].exterior().0[1].x;
+    assert!(corner(&p.buffer(-0.1)) - 0.9 > 1e-7);
+    assert!(f64::abs(corner(&p.buffer_with(-0.1, &BufferOptions::new().tolerance(1e-5))) - 0.9) < 1e-9);
+
+    let p = Polygon::new(LineString::from(vec![(0., 0.), (20., 0.), (20., 20.000001), (20.000001, 40.), (0., 40.)]), vec![]);
+    assert_eq!(p.buffer(-5.).0[0].exterior().0.len(), 6);
+    assert_eq!(p.buffer_with(-5., &BufferOptions::new().tolerance(1e-4)).0[0].exterior().0.len(), 5);
+}
+
+#[test]
+fn single_precision(){
+    use geo::MapCoords;
+    let to_f32 = |g: &Geometry| g.map_coords(|c| geo_types::coord!{x: c.x as f32, y: c.y as f32});
+    let exterior = LineString::from(vec![(0., 0.), (10., 0.), (10., 6.), (6., 6.), (6., 3.), (4., 3.), (4., 6.), (0., 6.)]);
+    let hole = LineString::from(vec![(1., 1.), (1., 2.), (3., 2.), (3., 1.)]);
+    let inputs: Vec<Geometry> = vec![
+        Polygon::new(exterior.clone(), vec![hole]).into(),
+        LineString::from(vec![(0., 0.), (4., 0.), (4., 3.), (1., 3.)]).into(),
+        MultiPoint::from(vec![(0., 0.), (1.5, 0.), (5., 5.)]).into(),
+    ];
+    let round = BufferOptions::new().join_style(JoinStyle::Round).end_cap(EndCapStyle::Round);
+    for g in &inputs{
+        let g32 = to_f32(g);
+        for d in [-1.2, -0.7, -0.3, 0.4, 1.3]{
+            for opts in [BufferOptions::new(), round]{
+                let expected = g.buffer_with(d, &opts);
+                let res: MultiPolygon<f32> = g32.buffer_with(d as f32, &opts);
+                assert_eq!(res.0.len(), expected.0.len());
+                assert!(f32::abs(res.unsigned_area() - expected.unsigned_area() as f32) < 1e-4*(1. + expected.unsigned_area() as f32));
+            }
+        }
+    }
+
+    // Parcels in projected coordinates keep their shape as long as the offset vertices are representable in `f32`.
+    let p: Geometry = Polygon::new(exterior, vec![]).into();
+    let p32 = to_f32(&p).map_coords(|c| geo_types::coord!{x: c.x + 500000., y: c.y + 1000000.});
+    for d in [-1.25, -0.75, 0.5]{
+        let expected = p.buffer(d);
+        let res = p32.buffer(d as f32);
+        assert_eq!(res.0.len(), expected.0.len());
+        assert!(f32::abs(res.unsigned_area() - expected.unsigned_area() as f32) < 1e-2);
+    }
+    let square = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    let square32 = Polygon::new(LineString::from(vec![(0f32, 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]);
+    assert_eq!(skeleton_of_polygon_to_linestring(&square32, true).len(), skeleton_of_polygon_to_linestring(&square, true).len());
+}
+
+#[cfg(feature = "exact")]
+#[test]
+fn exact_skeleton(){
+    // The inradius of a regular octagon, at which all the vertices of the wavefront meet, up to the rounding of the edge directions.
+    let n = 8;
+    let octagon = Polygon::new(LineString::from((0..n).map(|i| {
+        let a = 2.*std::f64::consts::PI*(i as f64)/(n as f64);
+        (a.cos(), a.sin())
+    }).collect::<Vec<_>>()), vec![]);
+    let inradius = f64::cos(std::f64::consts::PI/(n as f64));
+    assert_eq!(octagon.buffer(-inradius*(1. - 1e-9)).0.len(), 1);
+    assert!(octagon.buffer(-inradius).0.is_empty());
+
+    let rect = Polygon::new(LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.)]), vec![]);
+    assert_area(&rect.buffer(-0.999999), 2.000002*0.000002, 1e-12);
+    assert!(rect.buffer(-1.).0.is_empty());
+
+    // A reflex vertex meeting another reflex vertex exactly.
+    let squares = MultiPolygon::new(vec![
+        Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)]), vec![]),
+        Polygon::new(LineString::from(vec![(3., 3.), (5., 3.), (5., 5.), (3., 5.)]), vec![]),
+    ]);
+    let res = squares.buffer(1.);
+    assert_eq!(res.0.len(), 1);
+    assert_area(&res, 2.*16. - 1., 1e-9);
+}
@@ -1,82 +1,87 @@
 use core::ops::{Add, Sub, Div, Mul};
 
-use crate::util::{feq, Ray};
+use geo::GeoFloat;
+use num_traits::Float;
+
+use crate::util::{default_tolerance, feq, Ray};
 
 /// This structure conceptually represents a point or a vector on 
 /// the 2-dimensional Cartesian plane.
 /// 
 /// It may be vary on the context which represents which.
+/// 
+/// The components are of any floating point type `T` of `geo` (e.g. `f32`), which is `f64` by default.
 #[derive(Clone, Default, Debug, Copy, PartialEq, PartialOrd)]
-pub struct Coordinate(
+pub struct Coordinate<T: GeoFloat = f64>(
     /// x-component of the Cartesian coordinates.
-    pub f64, 
+    pub T, 
     /// y-component of the Cartesian coordinates.
-    pub f64,
+    pub T,
 );
 
-impl From<Coordinate> for (f64, f64){
-    fn from(item: Coordinate) -> (f64, f64){
+impl<T: GeoFloat> From<Coordinate<T>> for (T, T){
+    fn from(item: Coordinate<T>) -> (T, T){
         (item.0, item.1)
     }
 }
 
-impl From<(f64, f64)> for Coordinate {
-    fn from(item: (f64, f64)) -> Coordinate {
+impl<T: GeoFloat> From<(T, T)> for Coordinate<T> {
+    fn from(item: (T, T)) -> Coordinate<T> {
         Coordinate(item.0, item.1)
     }
 }
 
-impl From<geo_types::Coord<f64>> for Coordinate{
-    fn from(value: geo_types::Coord<f64>) -> Self {
+impl<T: GeoFloat> From<geo_types::Coord<T>> for Coordinate<T>{
+    fn from(value: geo_types::Coord<T>) -> Self {
         Coordinate(value.x, value.y)
     }
 }
 
-impl From<Coordinate> for geo_types::Coord<f64>{
-    fn from(value: Coordinate) -> geo_types::Coord<f64> {
+impl<T: GeoFloat> From<Coordinate<T>> for geo_types::Coord<T>{
+    fn from(value: Coordinate<T>) -> geo_types::Coord<T> {
         geo_types::geometry::Coord{x: value.0, y: value.1}
     }
 }
 
-impl Add for Coordinate{
+impl<T: GeoFloat> Add for Coordinate<T>{
     type Output = Self;
     fn add(self, rhs: Self) -> Self{
         Self(self.0+rhs.0, self.1+rhs.1)
     }
 }
 
-impl Sub for Coordinate{
+impl<T: GeoFloat> Sub for Coordinate<T>{
     type Output = Self;
     fn sub(self, rhs: Self) -> Self{
         Self(self.0-rhs.0, self.1-rhs.1)
     }
 }
 
-impl Mul<f64> for Coordinate{
+impl<T: GeoFloat> Mul<T> for Coordinate<T>{
     type Output = Self;
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self(self.0*rhs, self.1*rhs)
     }
 }
 
-impl Div<f64> for Coordinate{
+impl<T: GeoFloat> Div<T> for Coordinate<T>{
     type Output = Self;
-    fn div(self, rhs: f64) -> Self::Output {
-        if rhs == 0. {return self;}
+    fn div(self, rhs: T) -> Self::Output {
+        if rhs.is_zero() {return self;}
         Self(self.0/rhs, self.1/rhs)
     }
 }
 
-impl Div<Coordinate> for Coordinate{
-    type Output = f64;
+impl<T: GeoFloat> Div<Coordinate<T>> for Coordinate<T>{
+    type Output = T;
     fn div(self, rhs: Self) -> Self::Output{
-        if rhs.0 == 0. && rhs.1 == 0. {return 0.;}
-        else if rhs.1 == 0. {return self.0/rhs.0;}
-        else {return self.1/rhs.1;}
+        if rhs.0.is_zero() && rhs.1.is_zero() {T::zero()}
+        else if rhs.1.is_zero() {self.0/rhs.0}
+        else {self.1/rhs.1}
     }
 }
 
-impl Coordinate{
+impl<T: GeoFloat> Coordinate<T>{
     /// Creates and returns a [Coordinate] w.r.t. the given argument.
     /// 
     /// # Argument
@@ -90,8 +95,8 @@ impl Coordinate{
     /// let c1 = geo_buffer::Coordinate::new(3., 4.);
     /// assert_eq!(c1, (3., 4.).into());
     /// ```
-    pub fn new(x: f64, y: f64) -> Self{
-        Self{0: x, 1: y}
+    pub fn new(x: T, y: T) -> Self{
+        Self(x, y)
     }
 
     /// Returns a tuple wihch has values of each component.
@@ -103,7 +108,7 @@ impl Coordinate{
     /// let t1 = c1.get_val();
     /// assert_eq!(t1, (3., 4.));
     /// ```
-    pub fn get_val(&self) -> (f64, f64){
+    pub fn get_val(&self) -> (T, T){
         (self.0, self.1)
     }
 
@@ -133,7 +138,7 @@ impl Coordinate{
     /// + This operation is linear.
     /// + This operation is commutative.
     /// 
-    pub fn inner_product(&self, rhs: &Self) -> f64{
+    pub fn inner_product(&self, rhs: &Self) -> T{
         self.0*rhs.0 + self.1*rhs.1
     }
 
@@ -163,10 +168,10 @@ impl Coordinate{
     /// + This operation is linear.
     /// + This operation is *not* commutative. (More precisely, it is anti-commutative.)
     /// + The sign of cross product indicates the orientation of **a** and **b**. If **a** lies before **b** in
-    /// the counter-clockwise (CCW for short) ordering, the sign of the result will be positive. If **a** lies after **b** in CCW ordering,
-    /// the sign will be negative. The result will be zero if two vectors are colinear. (I.e. lay on the same line.)
+    ///   the counter-clockwise (CCW for short) ordering, the sign of the result will be positive. If **a** lies after **b** in CCW ordering,
+    ///   the sign will be negative. The result will be zero if two vectors are colinear. (I.e. lay on the same line.)
     /// 
-    pub fn outer_product(&self, rhs: &Self) -> f64{
+    pub fn outer_product(&self, rhs: &Self) -> T{
         self.0*rhs.1-self.1*rhs.0
     }
 
@@ -178,7 +183,7 @@ impl Coordinate{
     /// let c1 = geo_buffer::Coordinate::new(3., 4.);
     /// assert_eq!(c1.norm(), 5.);
     /// ```
-    pub fn norm(&self) -> f64{
+    pub fn norm(&self) -> T{
         self.inner_product(self).sqrt()
     }
 
@@ -191,8 +196,8 @@ impl Coordinate{
     /// let c2 = geo_buffer::Coordinate::new(7., 7.);
     /// assert_eq!(c1.dist_coord(&c2), 5.);
     /// ```
-    pub fn dist_coord(&self, rhs: &Coordinate) -> f64{
-        f64::sqrt((self.0-rhs.0)*(self.0-rhs.0) + (self.1-rhs.1)*(self.1-rhs.1))
+    pub fn dist_coord(&self, rhs: &Coordinate<T>) -> T{
+        T::sqrt((self.0-rhs.0)*(self.0-rhs.0) + (self.1-rhs.1)*(self.1-rhs.1))
     }
     
     /// Returns the distance from `self` to the given ray.
@@ -210,9 +215,9 @@ impl Coordinate{
     /// assert_eq!(c1.dist_ray(&r1), 2.4);
     /// ```
     /// 
-    pub fn dist_ray(&self, rhs: &Ray) -> f64{
-        if rhs.is_degenerated() {return self.dist_coord(&rhs.origin);}
-        return f64::abs((*self-rhs.origin).outer_product(&rhs.angle)) / rhs.angle.norm();
+    pub fn dist_ray(&self, rhs: &Ray<T>) -> T{
+        if rhs.is_degenerated(default_tolerance::<T>()) {return self.dist_coord(&rhs.origin);}
+        Float::abs((*self-rhs.origin).outer_product(&rhs.angle)) / rhs.angle.norm()
     }
 
     /// Checks whether the given two Cartesian coordinates are the same (by the equality test with a small epsilon).
@@ -241,7 +246,14 @@ impl Coordinate{
     /// let c4 = c1 + c2;
     /// assert_eq!(c3, c4); // should panic since 0.1 + 0.2 != 0.3 due to floating point errors
     /// ```
+    // `PartialEq` is derived with the exact comparison, thus this one keeps its own name.
+    #[allow(clippy::should_implement_trait)]
     pub fn eq(&self, rhs: &Self) -> bool{
-        feq(self.0, rhs.0) && feq(self.1, rhs.1)
+        self.eq_within(rhs, default_tolerance::<T>())
+    }
+
+    /// Same as [Coordinate::eq()], where the given tolerance is used instead of the default one.
+    pub(crate) fn eq_within(&self, rhs: &Self, eps: f64) -> bool{
+        feq(self.0, rhs.0, eps) && feq(self.1, rhs.1, eps)
     }
 }
\ No newline at end of file
@@ -5,36 +5,149 @@
 mod coordinate;
 mod ray;
 
+use geo::{BooleanOps, GeoFloat};
+use geo::bool_ops::BoolOpsNum;
+use geo::orient::{Orient, Direction};
+use geo_types::{Polygon, MultiPolygon};
+use num_traits::Float;
+
+
 pub use coordinate::Coordinate;
 pub use ray::Ray;
 
-const EPS: f64 = 1e-9;
+/// This trait represents the floating point types of the coordinates which this crate can buffer, i.e. the [GeoFloat] types
+/// on which the boolean operations of `geo` are available. In practice, these are `f32` and `f64`.
+pub trait BufferFloat: GeoFloat + BoolOpsNum {}
+
+impl<T: GeoFloat + BoolOpsNum> BufferFloat for T {}
+
+/// The default tolerance below which two values are considered equal by [feq()] and the other comparisons, for `f64`.
+/// Since every input is normalized before it is buffered, this is relative to the extent of the input.
+pub(crate) const EPS: f64 = 1e-9;
 
-pub(crate) fn feq(x: f64, y: f64) -> bool{
-    f64::abs(x-y) < EPS
+/// Returns the default tolerance for the coordinates of type `T`. It is [EPS] for `f64`, and is scaled by the square root
+/// of the ratio of the machine epsilons for the other types, e.g. about `2.3e-5` for `f32`.
+pub(crate) fn default_tolerance<T: GeoFloat>() -> f64{
+    let ratio = to_f64(T::epsilon()) / f64::EPSILON;
+    EPS * f64::max(ratio, 1.).sqrt()
 }
 
-pub(crate) fn fneq(x: f64, y: f64) -> bool{
-    !feq(x, y)
+/// Converts the given `f64` value into `T`, e.g. a constant in the computations generic over the coordinate type.
+pub(crate) fn float<T: GeoFloat>(x: f64) -> T{
+    T::from(x).unwrap_or_else(T::nan)
 }
 
-pub(crate) fn fgt(x: f64, y: f64) -> bool{
-    if feq(x, y) {return false;}
+/// Converts the given value of type `T` into `f64`, e.g. to pass it to [ArcResolution][crate::ArcResolution].
+pub(crate) fn to_f64<T: GeoFloat>(x: T) -> f64{
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+// The comparisons below consider two values equal if they differ by less than the given tolerance `eps`, which is passed down
+// explicitly from where the input is normalized. (See [normalized()][crate::normalization::normalized].)
+
+pub(crate) fn feq<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    Float::abs(x-y) < float(eps)
+}
+
+pub(crate) fn fneq<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    !feq(x, y, eps)
+}
+
+pub(crate) fn fgt<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    if feq(x, y, eps) {return false;}
     x > y
 }
 
 #[allow(dead_code)]
-pub(crate) fn flt(x: f64, y: f64) -> bool{
-    if feq(x, y) {return false;}
+pub(crate) fn flt<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    if feq(x, y, eps) {return false;}
     x < y
 }
 
-pub(crate) fn fgeq(x: f64, y: f64) -> bool{
-    if feq(x, y) {return true;}
+#[allow(dead_code)]
+pub(crate) fn fgeq<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    if feq(x, y, eps) {return true;}
     x > y
 }
 
-pub(crate) fn fleq(x: f64, y: f64) -> bool{
-    if feq(x, y) {return true;}
+pub(crate) fn fleq<T: GeoFloat>(x: T, y: T, eps: f64) -> bool{
+    if feq(x, y, eps) {return true;}
     x < y
-}
\ No newline at end of file
+}
+
+/// Returns the orientation of the given three points, i.e. `1` if they are in counter-clockwise order, `-1` if they are in
+/// clockwise order and `0` if they are collinear.
+/// 
+/// Unlike the comparisons with [EPS], the result is exact for any floating point input regardless of the scale of the coordinates,
+/// since it is decided by the adaptive predicate of the `robust` crate. (The coordinates are converted to `f64` exactly for `f32`.)
+pub(crate) fn orient2d<T: GeoFloat>(a: Coordinate<T>, b: Coordinate<T>, c: Coordinate<T>) -> i32{
+    let coord = |p: Coordinate<T>| robust::Coord{x: to_f64(p.0), y: to_f64(p.1)};
+    let det = robust::orient2d(coord(a), coord(b), coord(c));
+    if det > 0. {1} else if det < 0. {-1} else {0}
+}
+
+/// Returns the exact sign of the cross product of the given vectors. (See [orient2d()].)
+pub(crate) fn cross_sign<T: GeoFloat>(u: Coordinate<T>, v: Coordinate<T>) -> i32{
+    orient2d(Coordinate(T::zero(), T::zero()), u, v)
+}
+
+/// Returns the points on the circular arc of radius `radius` around the starting point of `from`,
+/// sweeping from the direction of `from` to the direction of `to`.
+/// Both rays should be normalized and share the same starting point.
+/// 
+/// The arc is swept counter-clockwise if `orient` is `true`, and clockwise otherwise. The arc is divided into the least number of
+/// segments of equal angle which does not exceed `step` (in radian), thus the result is symmetric about the bisector of the arc.
+/// Both endpoints of the arc are included and coincide exactly with the endpoints of `from` and `to` scaled by `radius`.
+pub(crate) fn arc_points<T: GeoFloat>(from: Ray<T>, to: &Ray<T>, radius: T, step: f64, orient: bool) -> Vec<Coordinate<T>>{
+    let mut sweep = to_f64(T::atan2(from.angle.outer_product(&to.angle), from.angle.inner_product(&to.angle)));
+    if !orient {sweep = -sweep;}
+    if sweep < 0. {sweep += 2.*std::f64::consts::PI;}
+    let segments = f64::max(f64::ceil(sweep/step - EPS), 1.) as usize;
+    let delta = if orient {sweep/segments as f64} else {-sweep/segments as f64};
+    let mut ret = Vec::with_capacity(segments+1);
+    ret.push(from.point_by_ratio(radius));
+    for i in 1..segments{
+        ret.push(from.rotate_by(float(delta*i as f64)).point_by_ratio(radius));
+    }
+    ret.push(to.point_by_ratio(radius));
+    ret
+}
+
+/// Returns the ratio of the length of the miter to the offset distance at a corner whose edges have the given (normalized) outward normals.
+pub(crate) fn miter_ratio<T: GeoFloat>(normal1: Coordinate<T>, normal2: Coordinate<T>, eps: f64) -> f64{
+    let denom = T::one() + normal1.inner_product(&normal2);
+    if fleq(denom, T::zero(), eps) {return f64::INFINITY;}
+    to_f64((normal1 + normal2).norm() / denom)
+}
+
+/// Returns the corner points of the miter joint around `origin` which is clipped by a bevel perpendicular to the bisector at the distance
+/// `miter_limit * radius` from `origin`. The edges of the corner have the given (normalized) outward normals and are offset by `radius`.
+/// 
+/// If `miter_limit` is so small that the bevel would cut the offset edges themselves, the bevel joins the endpoints of the offset edges instead.
+/// This function assumes that the edges are not antiparallel.
+pub(crate) fn limited_miter_points<T: GeoFloat>(origin: Coordinate<T>, normal1: Coordinate<T>, normal2: Coordinate<T>, radius: T, miter_limit: f64) -> Vec<Coordinate<T>>{
+    let bisector = (normal1 + normal2) / (normal1 + normal2).norm();
+    let clip_distance = T::max(float::<T>(miter_limit)*radius, radius*normal1.inner_product(&bisector));
+    let clip_line = Ray{origin: origin + bisector*clip_distance, angle: Coordinate(-bisector.1, bisector.0)};
+    let edge1 = Ray{origin: origin + normal1*radius, angle: Coordinate(-normal1.1, normal1.0)};
+    let edge2 = Ray{origin: origin + normal2*radius, angle: Coordinate(-normal2.1, normal2.0)};
+    vec![edge1.intersect(&clip_line), edge2.intersect(&clip_line)]
+}
+
+/// Returns the union of the given polygons as a single `MultiPolygon`. The given polygons may overlap each other.
+/// The exterior ring of each result polygon is oriented counter-clockwise and the interior rings are oriented clockwise.
+pub(crate) fn union_polygons<T: BufferFloat>(polygons: Vec<Polygon<T>>) -> MultiPolygon<T>{
+    let mut pieces = polygons.into_iter().map(|p| MultiPolygon::new(vec![p])).collect::<Vec<_>>();
+    while pieces.len() > 1{
+        let mut merged = Vec::with_capacity(pieces.len().div_ceil(2));
+        let mut iter = pieces.into_iter();
+        while let Some(lhs) = iter.next(){
+            match iter.next(){
+                Some(rhs) => merged.push(lhs.union(&rhs)),
+                None => merged.push(lhs),
+            }
+        }
+        pieces = merged;
+    }
+    pieces.pop().unwrap_or_else(|| MultiPolygon::new(vec![])).orient(Direction::Default)
+}
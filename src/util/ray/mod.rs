@@ -1,4 +1,7 @@
 use std::fmt;
+
+use geo::GeoFloat;
+
 use crate::util::*;
 
 /// This structure conceptually represents a half-line (which also known as "Ray").
@@ -16,19 +19,21 @@ use crate::util::*;
 /// 
 /// We can also think of a ray as the locus of a moving point at a constant velocity from the starting point **r<sub>0</sub>** as time passes.
 /// In this case, the location of the point after time *t* (*t* ≥ 0) is equal to **r<sub>0</sub>** + *t***v**.
+/// 
+/// Like [Coordinate], the components are of any floating point type `T` of `geo`, which is `f64` by default.
 #[derive(Clone, Default, Debug, Copy)]
-pub struct Ray{
-    pub(crate) origin: Coordinate,
-    pub(crate) angle: Coordinate,
+pub struct Ray<T: GeoFloat = f64>{
+    pub(crate) origin: Coordinate<T>,
+    pub(crate) angle: Coordinate<T>,
 }
 
-impl fmt::Display for Ray{
+impl<T: GeoFloat + fmt::Display> fmt::Display for Ray<T>{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Origin : (x, y) = ({}, {}) / Angle : (dx, dy) = ({}, {})", self.origin.0, self.origin.1, self.angle.0, self.angle.1)
     }
 }
 
-impl Ray{
+impl<T: GeoFloat> Ray<T>{
     /// Creates and returns a [Ray] w.r.t. the given arguments.
     ///  
     /// # Arguments
@@ -51,7 +56,7 @@ impl Ray{
     /// 
     /// ```
     /// 
-    pub fn new(src: Coordinate, dst: Coordinate) -> Self{
+    pub fn new(src: Coordinate<T>, dst: Coordinate<T>) -> Self{
         Self {
             origin: src,
             angle: dst-src,
@@ -72,8 +77,8 @@ impl Ray{
     /// assert!(c1.eq(&r1.point()));
     /// 
     /// ```
-    pub fn point(&self) -> Coordinate{
-        self.point_by_ratio(0.)
+    pub fn point(&self) -> Coordinate<T>{
+        self.point_by_ratio(T::zero())
     }
 
     /// Returns the value of parameterized equation **r<sub>0</sub>** + *t***v** by the given ratio *t*.
@@ -89,25 +94,25 @@ impl Ray{
     /// 
     /// assert!(r1.point_by_ratio(2.).eq(&(3., 4.).into()));
     /// ```
-    pub fn point_by_ratio(&self, ratio: f64) -> Coordinate{
+    pub fn point_by_ratio(&self, ratio: T) -> Coordinate<T>{
         self.origin + self.angle*ratio
     }
 
-    pub(crate) fn bisector(&self, rhs: &Ray, origin: Coordinate, orient: bool) -> Self{
+    pub(crate) fn bisector(&self, rhs: &Ray<T>, origin: Coordinate<T>, orient: bool, eps: f64) -> Self{
         let mut ray = self.angle*rhs.angle.norm() + rhs.angle*self.angle.norm();
-        if feq(ray.0, 0.) && feq(ray.1, 0.) {
+        if feq(ray.0, T::zero(), eps) && feq(ray.1, T::zero(), eps) {
             ray = (-self.angle.1, self.angle.0).into();
-            if orient {ray = ray * -1.;}
+            if orient {ray = ray * -T::one();}
         }
         else  {
-            if orient == true && self.angle.outer_product(&ray) > 0.0 {ray = ray*-1.0;}
-            if orient == false && self.angle.outer_product(&ray) < 0.0 {ray = ray*-1.0;}
+            if orient && cross_sign(self.angle, ray) > 0 {ray = ray*-T::one();}
+            if !orient && cross_sign(self.angle, ray) < 0 {ray = ray*-T::one();}
         }
         // else {
         //     if orient == true && tmp_angle.outer_product(&ray) > 0.0 {ray = ray*-1.0;}
         //     if orient == false && tmp_angle.outer_product(&ray) < 0.0 {ray = ray*-1.0;}
         // }
-        Self { origin: origin, angle: ray }
+        Self { origin, angle: ray }
     }
 
     /// Checks whether `self` contains the given Cartesian coordinate.
@@ -131,9 +136,14 @@ impl Ray{
     /// 
     /// assert!(r1.is_contain(&(3., 4.).into()));
     /// ```
-    pub fn is_contain(&self, rhs: &Coordinate) -> bool {
-        if self.is_degenerated() {return feq(self.origin.0, rhs.0) && feq(self.origin.1, rhs.1);}
-        feq((*rhs - self.origin).outer_product(&self.angle), 0.)
+    pub fn is_contain(&self, rhs: &Coordinate<T>) -> bool {
+        self.is_contain_within(rhs, default_tolerance::<T>())
+    }
+
+    /// Same as [Ray::is_contain()], where the given tolerance is used instead of the default one.
+    pub(crate) fn is_contain_within(&self, rhs: &Coordinate<T>, eps: f64) -> bool {
+        if self.is_degenerated(eps) {return feq(self.origin.0, rhs.0, eps) && feq(self.origin.1, rhs.1, eps);}
+        feq((*rhs - self.origin).outer_product(&self.angle), T::zero(), eps)
     }
 
     /// Checks whether the given two rays are intersecting with each other.
@@ -155,17 +165,23 @@ impl Ray{
     /// 
     /// assert!(r1.is_contain(&(3., 4.).into()));
     /// ```
-    pub fn is_intersect(&self, rhs: &Ray) -> bool {
-        let op = self.angle.outer_product(&rhs.angle);
-        if feq(op, 0.0){
-            if self.is_contain(&rhs.origin) {return true;}
-            if rhs.is_contain(&self.origin) {return true;}
+    pub fn is_intersect(&self, rhs: &Ray<T>) -> bool {
+        self.is_intersect_within(rhs, default_tolerance::<T>())
+    }
+
+    /// Same as [Ray::is_intersect()], where the given tolerance is used instead of the default one.
+    pub(crate) fn is_intersect_within(&self, rhs: &Ray<T>, eps: f64) -> bool {
+        let op = cross_sign(self.angle, rhs.angle);
+        if op == 0{
+            if self.is_contain_within(&rhs.origin, eps) {return true;}
+            if rhs.is_contain_within(&self.origin, eps) {return true;}
             return false;
         }
-        let i = (rhs.origin - self.origin).outer_product(&rhs.angle) / self.angle.outer_product(&rhs.angle);
-        let j = (rhs.origin - self.origin).outer_product(&self.angle) / self.angle.outer_product(&rhs.angle);
-        if fgeq(i, 0.) && fgeq(j, 0.) {return true;}
-        false
+        // The rays meet at `self.origin + self.angle*i = rhs.origin + rhs.angle*j`, where the signs of `i` and `j` are decided
+        // by [Ray::orientation()].
+        let i = rhs.orientation(&self.origin) * op;
+        let j = -self.orientation(&rhs.origin) * op;
+        i >= 0 && j >= 0
     }
 
     /// Returns a common point of the given rays. If they have more than 2 common points, then returns a
@@ -189,14 +205,19 @@ impl Ray{
     /// assert!(r1.intersect(&r2).eq(&(2., 2.).into()));
     /// 
     /// ```
-    pub fn intersect(&self, rhs: &Ray) -> Coordinate{
+    pub fn intersect(&self, rhs: &Ray<T>) -> Coordinate<T>{
+        self.intersect_within(rhs, default_tolerance::<T>())
+    }
+
+    /// Same as [Ray::intersect()], where the given tolerance is used instead of the default one.
+    pub(crate) fn intersect_within(&self, rhs: &Ray<T>, eps: f64) -> Coordinate<T>{
         let op = self.angle.outer_product(&rhs.angle);
-        if feq(op, 0.) {
-            if self.is_contain(&rhs.origin) {
-                if fgt((rhs.origin - self.origin)/self.angle, 0.) {return rhs.origin;}
+        if feq(op, T::zero(), eps) {
+            if self.is_contain_within(&rhs.origin, eps) {
+                if fgt((rhs.origin - self.origin)/self.angle, T::zero(), eps) {return rhs.origin;}
                 else {return self.origin;}
             }
-            return (self.origin + rhs.origin)/2.0;
+            return (self.origin + rhs.origin)/float::<T>(2.);
         }
         let i = (rhs.origin - self.origin).outer_product(&rhs.angle) / self.angle.outer_product(&rhs.angle);
         self.origin + self.angle*i
@@ -224,14 +245,19 @@ impl Ray{
     /// 
     /// assert!(r1.is_parallel(&r2));
     /// ```
-    pub fn is_parallel(&self, rhs: &Ray) -> bool {
+    pub fn is_parallel(&self, rhs: &Ray<T>) -> bool {
+        self.is_parallel_within(rhs, default_tolerance::<T>())
+    }
+
+    /// Same as [Ray::is_parallel()], where the given tolerance is used instead of the default one.
+    pub(crate) fn is_parallel_within(&self, rhs: &Ray<T>, eps: f64) -> bool {
         let op = self.angle.outer_product(&rhs.angle);
-        if feq(op, 0.0) && !self.is_contain(&rhs.origin) {return true;}
-        return false;
+        if feq(op, T::zero(), eps) && !self.is_contain_within(&rhs.origin, eps) {return true;}
+        false
     }
 
-    pub(crate) fn is_degenerated(&self) -> bool {
-        if feq(self.angle.0, 0.) && feq(self.angle.1, 0.) {true} else {false}
+    pub(crate) fn is_degenerated(&self, eps: f64) -> bool {
+        feq(self.angle.0, T::zero(), eps) && feq(self.angle.1, T::zero(), eps)
     }
 
     /// Normalizes the given `Ray`. The magnitude of the 'velocity' becomes 1. Does nothing if it is 0.
@@ -249,15 +275,17 @@ impl Ray{
     /// assert!(r1.point_by_ratio(1.).eq(&(0.6, 0.8).into()));
     /// ```
     pub fn normalize(&mut self) {
-        if self.is_degenerated() {return;}
+        if self.is_degenerated(default_tolerance::<T>()) {return;}
         self.angle = self.angle/self.angle.norm();
     }
 
-    pub(crate) fn orientation(&self, rhs: &Coordinate) -> i32 {
-        let res = self.angle.outer_product(&(*rhs - self.origin));
-        if feq(res, 0.) {return 0;}
-        if fgt(res, 0.) {return 1;}
-        return -1;
+    /// Returns `1` if the given point lies on the left side of the extended line of `self`, `-1` if it lies on the right side,
+    /// and `0` if it lies on the line.
+    ///
+    /// The decision is exact for the line through `self.origin` and the point `self.origin + self.angle` rounded to `T` (see [orient2d()]),
+    /// which may differ slightly from the extended line of `self`. Use [Ray::is_contain()] to check whether a point lies on the line with the tolerance.
+    pub(crate) fn orientation(&self, rhs: &Coordinate<T>) -> i32 {
+        orient2d(self.origin, self.origin + self.angle, *rhs)
     }
 
     /// Returns the reversed ray of the given ray. The returned ray has the same starting point
@@ -278,7 +306,7 @@ impl Ray{
     pub fn reverse(&self) -> Self{
         Self{
             origin: self.origin,
-            angle: self.angle*-1.,
+            angle: self.angle*-T::one(),
         }
     }
 
@@ -297,9 +325,9 @@ impl Ray{
     /// 
     /// assert!(r2.point_by_ratio(1.).eq(&(-4., 3.).into()));
     /// ```
-    pub fn rotate_by(&self, angle: f64) -> Self{
-        let nx = self.angle.0*f64::cos(angle) - self.angle.1*f64::sin(angle);
-        let ny = self.angle.0*f64::sin(angle) + self.angle.1*f64::cos(angle);
+    pub fn rotate_by(&self, angle: T) -> Self{
+        let nx = self.angle.0*T::cos(angle) - self.angle.1*T::sin(angle);
+        let ny = self.angle.0*T::sin(angle) + self.angle.1*T::cos(angle);
         Self { origin: self.origin, angle: (nx, ny).into() }
     }
 }
\ No newline at end of file
@@ -0,0 +1,209 @@
+use std::fmt;
+
+use geo::{Area, BooleanOps, BoundingRect, CoordinatePosition, GeoFloat, InteriorPoint, Intersects};
+use geo::coordinate_position::CoordPos;
+use geo::line_intersection::{line_intersection, LineIntersection};
+use geo_types::{Line, LineString, MultiPolygon, Polygon};
+
+use crate::util::{BufferFloat, Coordinate};
+use crate::error::BufferError;
+
+/// This enumeration represents the kinds of invalidity which prevent a (multi-)polygon from being buffered properly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidityKind{
+    /// A ring has fewer than three distinct vertices.
+    TooFewVertices,
+    /// A ring has two identical consecutive coordinates.
+    RepeatedPoint,
+    /// A ring crosses or touches itself, or two rings of the same polygon cross each other.
+    SelfIntersection,
+    /// An interior ring (hole) is not inside the exterior ring of its polygon.
+    HoleOutsideShell,
+    /// An interior ring (hole) is inside another hole of the same polygon.
+    NestedHoles,
+    /// The interiors of two polygons of a multi-polygon overlap each other.
+    OverlappingMembers,
+}
+
+/// This structure represents a single invalidity found by [validate_for_buffer()].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Invalidity<T: GeoFloat = f64>{
+    /// The kind of the invalidity.
+    pub kind: InvalidityKind,
+    /// The location where the invalidity occurs. For an empty ring, this is the origin.
+    pub location: Coordinate<T>,
+}
+
+impl<T: GeoFloat + fmt::Display> fmt::Display for Invalidity<T>{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        let desc = match self.kind{
+            InvalidityKind::TooFewVertices => "ring with fewer than three distinct vertices",
+            InvalidityKind::RepeatedPoint => "repeated point",
+            InvalidityKind::SelfIntersection => "self-intersection",
+            InvalidityKind::HoleOutsideShell => "hole outside of the shell",
+            InvalidityKind::NestedHoles => "hole inside another hole",
+            InvalidityKind::OverlappingMembers => "overlapping polygons",
+        };
+        write!(f, "{} at ({}, {})", desc, self.location.0, self.location.1)
+    }
+}
+
+fn check_ring<T: GeoFloat>(ring: &LineString<T>, ret: &mut Vec<Invalidity<T>>){
+    let Some(first) = ring.0.first() else {
+        ret.push(Invalidity{kind: InvalidityKind::TooFewVertices, location: Coordinate(T::zero(), T::zero())});
+        return;
+    };
+    for w in ring.0.windows(2){
+        if w[0] == w[1] {
+            ret.push(Invalidity{kind: InvalidityKind::RepeatedPoint, location: w[0].into()});
+        }
+    }
+    let mut distinct: Vec<Coordinate<T>> = Vec::new();
+    for c in &ring.0{
+        let c: Coordinate<T> = (*c).into();
+        if distinct.len() < 3 && !distinct.contains(&c) {distinct.push(c);}
+    }
+    if distinct.len() < 3 {
+        ret.push(Invalidity{kind: InvalidityKind::TooFewVertices, location: (*first).into()});
+    }
+}
+
+/// Reports the points where the rings of the given polygon cross or touch themselves, or cross each other.
+/// The points where a ring touches itself are not reported unless `self_touches` is `true`.
+fn check_crossings<T: GeoFloat>(polygon: &Polygon<T>, self_touches: bool, ret: &mut Vec<Invalidity<T>>){
+    let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors()).collect::<Vec<_>>();
+    let mut edges: Vec<(usize, usize, usize, Line<T>)> = Vec::new();
+    for (ri, ring) in rings.iter().enumerate(){
+        let lines = ring.lines().filter(|l| l.start != l.end).collect::<Vec<_>>();
+        let len = lines.len();
+        edges.extend(lines.into_iter().enumerate().map(|(i, l)| (ri, i, len, l)));
+    }
+    // Sweep the edges from left to right so that only the pairs whose x ranges overlap are tested.
+    let min_x = |l: &Line<T>| l.start.x.min(l.end.x);
+    edges.sort_by(|a, b| min_x(&a.3).total_cmp(&min_x(&b.3)).then((a.0, a.1).cmp(&(b.0, b.1))));
+    for (i, &(r1, i1, len1, l1)) in edges.iter().enumerate(){
+        let max_x = l1.start.x.max(l1.end.x);
+        for &(r2, i2, _, l2) in edges[i+1..].iter().take_while(|e| min_x(&e.3) <= max_x){
+            let Some(res) = line_intersection(l1, l2) else {continue};
+            let location = match res{
+                LineIntersection::SinglePoint { intersection, is_proper } => {
+                    if r1 == r2 {
+                        // Adjacent edges of a ring always share their common vertex.
+                        let (i1, i2) = (i1.min(i2), i1.max(i2));
+                        let adjacent = i2 == i1+1 || (i1 == 0 && i2+1 == len1);
+                        if adjacent || (!is_proper && !self_touches) {continue;}
+                    }
+                    else if !is_proper {continue;}
+                    intersection
+                },
+                LineIntersection::Collinear { intersection } => intersection.start,
+            };
+            ret.push(Invalidity{kind: InvalidityKind::SelfIntersection, location: location.into()});
+        }
+    }
+}
+
+fn check_holes<T: GeoFloat>(polygon: &Polygon<T>, ret: &mut Vec<Invalidity<T>>){
+    let shell = Polygon::new(polygon.exterior().clone(), vec![]);
+    for hole in polygon.interiors(){
+        if let Some(c) = hole.0.iter().find(|c| shell.coordinate_position(c) == CoordPos::Outside) {
+            ret.push(Invalidity{kind: InvalidityKind::HoleOutsideShell, location: (*c).into()});
+        }
+    }
+    let holes = polygon.interiors().iter().map(|hole| Polygon::new(hole.clone(), vec![])).collect::<Vec<_>>();
+    for (i, outer) in holes.iter().enumerate(){
+        for (j, inner) in holes.iter().enumerate(){
+            if i == j {continue;}
+            if let Some(c) = inner.exterior().0.iter().find(|c| outer.coordinate_position(c) == CoordPos::Inside) {
+                ret.push(Invalidity{kind: InvalidityKind::NestedHoles, location: (*c).into()});
+            }
+        }
+    }
+}
+
+fn check_overlaps<T: BufferFloat>(polygons: &[Polygon<T>], ret: &mut Vec<Invalidity<T>>){
+    for (i, p1) in polygons.iter().enumerate(){
+        for p2 in &polygons[i+1..]{
+            match (p1.bounding_rect(), p2.bounding_rect()){
+                (Some(r1), Some(r2)) if r1.intersects(&r2) => {},
+                _ => continue,
+            }
+            let common = p1.intersection(p2);
+            if common.unsigned_area() <= T::zero() {continue;}
+            if let Some(c) = common.interior_point() {
+                ret.push(Invalidity{kind: InvalidityKind::OverlappingMembers, location: c.0.into()});
+            }
+        }
+    }
+}
+
+/// Returns [BufferError::InvalidInput] if the given (cleaned) polygon is invalid, i.e. [validate_for_buffer()] reports an invalidity
+/// other than a ring touching itself at a point, which the buffer handles. (See [separate_touching()][crate::cleaning::separate_touching].)
+///
+/// The `relate` and boolean operations of `geo` assume valid polygons and may panic otherwise, thus each polygon is checked before them.
+pub(crate) fn check_polygon<T: GeoFloat>(polygon: &Polygon<T>) -> Result<(), BufferError>{
+    let mut ret = Vec::new();
+    check_crossings(polygon, false, &mut ret);
+    check_holes(polygon, &mut ret);
+    match ret.first(){
+        Some(x) => Err(BufferError::InvalidInput(format!("a polygon of the input is invalid ({:?}), see validate_for_buffer()", x.kind))),
+        None => Ok(()),
+    }
+}
+
+/// Returns [BufferError::InternalInconsistency] if the buffered (multi-)polygon assembled from a straight skeleton is invalid,
+/// i.e. its rings cross each other or its polygons overlap. The repeated points of the rounded joins and a ring touching itself
+/// at a point are not regarded as invalid.
+pub(crate) fn check_output<T: BufferFloat>(output: &MultiPolygon<T>) -> Result<(), BufferError>{
+    let mut ret = Vec::new();
+    for p in &output.0{
+        check_crossings(p, false, &mut ret);
+        check_holes(p, &mut ret);
+    }
+    // The boolean operations of `geo` assume valid polygons, thus the overlaps are checked only for the valid ones.
+    if ret.is_empty() {check_overlaps(&output.0, &mut ret);}
+    match ret.first(){
+        Some(x) => Err(BufferError::InternalInconsistency(format!("the buffered polygon is invalid ({:?} at ({:?}, {:?}))", x.kind, x.location.0, x.location.1))),
+        None => Ok(()),
+    }
+}
+
+/// This function inspects the given polygon or multi-polygon and reports every invalidity which prevents it from being
+/// buffered properly, i.e. rings with fewer than three distinct vertices, repeated consecutive points, self-intersections,
+/// holes outside of their shell or inside another hole, and overlapping polygons. Returns `Ok(())` if no invalidity is found.
+///
+/// Note that rings of different polygons, or different rings of the same polygon, may touch each other at a point.
+///
+/// # Arguments
+///
+/// + `input_geometry`: `Polygon` or `MultiPolygon` to inspect.
+///
+/// # Example
+///
+/// ```
+/// use geo_buffer::{validate_for_buffer, InvalidityKind, Coordinate};
+/// use geo::{Polygon, LineString};
+///
+/// let p1 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]), vec![],
+/// );
+/// assert!(validate_for_buffer(&p1).is_ok());
+///
+/// let p2 = Polygon::new(
+///     LineString::from(vec![(0., 0.), (1., 0.), (0., 1.), (1., 1.)]), vec![],
+/// );
+/// let res = validate_for_buffer(&p2).unwrap_err();
+/// assert_eq!(res[0].kind, InvalidityKind::SelfIntersection);
+/// assert_eq!(res[0].location, Coordinate(0.5, 0.5));
+/// ```
+pub fn validate_for_buffer<T: BufferFloat, G: Clone + Into<MultiPolygon<T>>>(input_geometry: &G) -> Result<(), Vec<Invalidity<T>>>{
+    let input_multi_polygon: MultiPolygon<T> = input_geometry.clone().into();
+    let mut ret = Vec::new();
+    for p in &input_multi_polygon.0{
+        std::iter::once(p.exterior()).chain(p.interiors()).for_each(|ring| check_ring(ring, &mut ret));
+        check_crossings(p, true, &mut ret);
+        check_holes(p, &mut ret);
+    }
+    check_overlaps(&input_multi_polygon.0, &mut ret);
+    if ret.is_empty() {Ok(())} else {Err(ret)}
+}
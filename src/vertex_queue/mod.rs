@@ -1,7 +1,10 @@
 use core::fmt;
 
+use geo::GeoFloat;
 use geo_types::Polygon;
 
+use crate::error::BufferError;
+
 #[derive(Clone, Debug, Copy, PartialEq, PartialOrd)]
 pub(crate) enum IndexType{
     PointerIndex(usize),
@@ -18,18 +21,18 @@ impl fmt::Display for IndexType{
 }
 
 impl IndexType{
-    pub(crate) fn get_index(&self) -> usize{
+    pub(crate) fn get_index(&self) -> Result<usize, BufferError>{
         if let IndexType::PointerIndex(res) = self{
-            return *res;
+            return Ok(*res);
         }
-        panic!("Expected IndexType::PointerIndex");
+        Err(BufferError::InternalInconsistency(format!("expected a pointer index but {self}")))
     }
 
-    pub(crate) fn get_real_index(&self) -> usize{
+    pub(crate) fn get_real_index(&self) -> Result<usize, BufferError>{
         if let IndexType::RealIndex(res) = self{
-            return *res;
+            return Ok(*res);
         }
-        panic!("Expected IndexType::RealIndex");
+        Err(BufferError::InternalInconsistency(format!("expected a real index but {self}")))
     }
 }
 
@@ -68,11 +71,11 @@ impl VertexQueue{
         Self { content: Vec::new(), start_vertex: Vec::new(), }
     }
 
-    pub(crate) fn initialize_from_polygon(&mut self, p: &Polygon){
+    pub(crate) fn initialize_from_polygon<T: GeoFloat>(&mut self, p: &Polygon<T>){
         self.initialize_from_polygon_vector(&vec![p.clone()])
     }
 
-    pub(crate) fn initialize_from_polygon_vector(&mut self, pv: &Vec<Polygon>){
+    pub(crate) fn initialize_from_polygon_vector<T: GeoFloat>(&mut self, pv: &Vec<Polygon<T>>){
         for p in pv{
             let offset = self.content.len();
             let len = p.exterior().0.len() - 1;
@@ -93,116 +96,98 @@ impl VertexQueue{
         }
     }
 
-    pub(crate) fn get_real_index(&self, cv: IndexType) -> usize{
-        if let IndexType::PointerIndex(cv) = cv{
-            return self.content[cv].index.get_real_index();
-        }
-        panic!("Expected parameter \"cv\" as IndexType::RealIndex")
+    pub(crate) fn get_real_index(&self, cv: IndexType) -> Result<usize, BufferError>{
+        self.content[cv.get_index()?].index.get_real_index()
     }
 
-    pub(crate) fn lv(&self, cv: IndexType) -> IndexType{
-        if let IndexType::PointerIndex(cv) = cv{
-            return self.content[cv].left;
-        }
-        panic!("Expected parameter \"cv\" as IndexType::PointerIndex");
+    pub(crate) fn lv(&self, cv: IndexType) -> Result<IndexType, BufferError>{
+        Ok(self.content[cv.get_index()?].left)
     }
 
-    pub(crate) fn rv(&self, cv: IndexType) -> IndexType{
-        if let IndexType::PointerIndex(cv) = cv{
-            return self.content[cv].right;
-        }
-        panic!("Expected parameter \"cv\" as IndexType::PointerIndex");
+    pub(crate) fn rv(&self, cv: IndexType) -> Result<IndexType, BufferError>{
+        Ok(self.content[cv.get_index()?].right)
     }
 
-    pub(crate) fn llv(&self, cv: IndexType) -> IndexType{
-        let cv = self.lv(cv);
+    pub(crate) fn llv(&self, cv: IndexType) -> Result<IndexType, BufferError>{
+        let cv = self.lv(cv)?;
         self.lv(cv)
     }
 
-    pub(crate) fn rrv(&self, cv: IndexType) -> IndexType{
-        let cv = self.rv(cv);
+    pub(crate) fn rrv(&self, cv: IndexType) -> Result<IndexType, BufferError>{
+        let cv = self.rv(cv)?;
         self.rv(cv)
     }
 
-    pub(crate) fn remove(&mut self, cv: IndexType) -> IndexType{
-        let tl = self.lv(cv);
-        let tr = self.rv(cv);
-        self.content[tl.get_index()].right = tr;
-        self.content[tr.get_index()].left = tl;
-        self.content[cv.get_index()].done = true;
-        tr
-    }
-
-    pub(crate) fn remove_and_set(&mut self, cv: IndexType, nv: IndexType) -> IndexType{
-        let cv = self.remove(cv);
-        if let IndexType::RealIndex(_) = nv{
-            self.content[cv.get_index()].index = nv;
+    /// Removes the nodes of the given pointer indices, and adds a new node for each of the given (real index, left node, right node)
+    /// between its left and right nodes. Returns the pointer indices of the new nodes.
+    ///
+    /// A new node whose left and right nodes are the same closes a cycle of two vertices, which has no area, thus both of them are
+    /// removed as well.
+    pub(crate) fn reconnect(&mut self, removed: &[usize], created: &[(usize, usize, usize)]) -> Result<Vec<IndexType>, BufferError>{
+        for &cv in removed{
+            self.content[cv].done = true;
         }
-        else {panic!("Expected parameter \"nv\" as IndexType::RealIndex");}
-        cv
-    }
-
-    pub(crate) fn split_and_set(&mut self, cv: IndexType, sv: IndexType, nv1: IndexType, nv2: IndexType) -> (IndexType, IndexType){
-        let new_node = Node::new(0, sv.get_index(), self.rv(cv).get_index());
-        let new_index = IndexType::PointerIndex(self.content.len());
-        self.content.push(new_node);
-        if let IndexType::RealIndex(_) = nv1{
-            self.content[cv.get_index()].index = nv1;
+        let mut ret = Vec::new();
+        for &(nv, left, right) in created{
+            let new_index = self.content.len();
+            self.content.push(Node::new(nv, left, right));
+            self.content[left].right = IndexType::PointerIndex(new_index);
+            self.content[right].left = IndexType::PointerIndex(new_index);
+            self.start_vertex.push(new_index);
+            ret.push(IndexType::PointerIndex(new_index));
         }
-        else {panic!("Expected parameter \"nv1\" as IndexType::RealIndex");}
-        if let IndexType::RealIndex(_) = nv2{
-            self.content[new_index.get_index()].index = nv2;
+        for cv in &ret{
+            let cv = cv.get_index()?;
+            let (lv, rv) = (self.content[cv].left.get_index()?, self.content[cv].right.get_index()?);
+            if lv == rv{
+                self.content[cv].done = true;
+                self.content[lv].done = true;
+            }
         }
-        else {panic!("Expected parameter \"nv2\" as IndexType::RealIndex");}
-        let svx = self.rv(sv); // right of sv (split vertex)
-        let cvx = self.rv(cv); // right of cv (current (anchor) vertex)
-        self.content[cvx.get_index()].left = new_index;
-        self.content[sv.get_index()].right = new_index;
-        self.content[cv.get_index()].right = svx;
-        self.content[svx.get_index()].left = cv;
-        self.start_vertex.push(cv.get_index());
-        self.start_vertex.push(new_index.get_index());
-        (cv, new_index)
-    }
-
-    pub(crate) fn cleanup(&mut self){
+        Ok(ret)
+    }
+
+    pub(crate) fn cleanup(&mut self) -> Result<(), BufferError>{
         let mut sv_idx = 0;
         let mut visit = vec![false; self.content.len()];
         while sv_idx < self.start_vertex.len(){
             let mut cur = self.start_vertex[sv_idx];
-            while self.content[cur].done == true && visit[cur] == false{
+            while self.content[cur].done && !visit[cur]{
                 visit[cur] = true;
-                cur = self.content[cur].right.get_index();
+                cur = self.content[cur].right.get_index()?;
             }
-            if visit[cur] == true || self.content[cur].left.get_index() == self.content[cur].right.get_index(){
+            if visit[cur] || self.content[cur].left.get_index()? == self.content[cur].right.get_index()?{
                 self.start_vertex.swap_remove(sv_idx);
                 continue;
             }
             self.start_vertex[sv_idx] = cur;
             visit[cur] = true;
-            cur = self.content[cur].right.get_index();
+            cur = self.content[cur].right.get_index()?;
             while cur != self.start_vertex[sv_idx] {
                 if visit[cur] {
-                    panic!("Something Worng in cleanup phase: cur {} from {}, sv {:?}", cur, sv_idx, self.start_vertex);
+                    return Err(BufferError::InternalInconsistency(format!("the wavefront is not a set of disjoint cycles: cur {} from {}, sv {:?}", cur, sv_idx, self.start_vertex)));
                 }
                 visit[cur] = true;
-                cur = self.content[cur].right.get_index();
+                cur = self.content[cur].right.get_index()?;
             }
             sv_idx += 1;
         }
+        Ok(())
     }
 
+    /// Iterates over the nodes of each cycle of the wavefront, yielding (cycle index, pointer index, real index) of each node.
+    /// Yields an error and stops if the links between the nodes are inconsistent.
     pub(crate) fn iter<'a>(&'a self) -> Iter<'a> {
-        Iter { item: self, sv_idx: 0, idx: usize::MAX, }
+        Iter { item: self, sv_idx: 0, idx: usize::MAX, steps: 0, }
     }
 }
 
 impl fmt::Display for VertexQueue{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[").unwrap();
-        for (e, ee, eee) in self.iter(){
-            let ee = ee.get_index();
-            write!(f, "({}, {}, {}), ", e, ee, eee).unwrap();
+        write!(f, "[")?;
+        for e in self.iter(){
+            let (e, ee, eee) = e.map_err(|_| fmt::Error)?;
+            write!(f, "({}, {}, {}), ", e, ee, eee)?;
         }
         write!(f, "]")
     }
@@ -212,26 +197,42 @@ pub(crate) struct Iter<'a> {
     item: &'a VertexQueue,
     sv_idx: usize,
     idx: usize,
+    steps: usize,
+}
+
+impl<'a> Iter<'a>{
+    fn step(&mut self) -> Result<(usize, IndexType, usize), BufferError>{
+        let Some(node) = self.item.content.get(self.idx) else {
+            return Err(BufferError::InternalInconsistency(format!("the wavefront refers to a missing node {}", self.idx)));
+        };
+        let ret = (self.sv_idx, IndexType::PointerIndex(self.idx), node.index.get_real_index()?);
+        self.idx = node.right.get_index()?;
+        self.steps += 1;
+        if self.item.start_vertex[self.sv_idx] == self.idx{
+            self.sv_idx += 1;
+            self.idx = usize::MAX;
+        }
+        else if self.steps >= self.item.content.len(){
+            return Err(BufferError::InternalInconsistency(format!("the wavefront from the node {} is not a cycle", self.item.start_vertex[self.sv_idx])));
+        }
+        Ok(ret)
+    }
 }
 
 impl<'a> Iterator for Iter<'a>{
-    type Item = (usize, IndexType, usize);
+    type Item = Result<(usize, IndexType, usize), BufferError>;
 
     fn next(&mut self) -> Option<Self::Item>{
         if self.idx == usize::MAX{
-            if self.sv_idx >= self.item.start_vertex.len() {return None;}
-            self.idx = self.item.start_vertex[self.sv_idx];
+            self.idx = *self.item.start_vertex.get(self.sv_idx)?;
+            self.steps = 0;
         }
-        let ret = match self.item.content[self.idx].index{
-            IndexType::RealIndex(rv) => rv,
-            _ => panic!("Expected IndexType::RealIndex"),
-        };
-        let ret = (self.sv_idx, IndexType::PointerIndex(self.idx), ret);
-        self.idx = self.item.content[self.idx].right.get_index();
-        if self.item.start_vertex[self.sv_idx] == self.idx{
-            self.sv_idx += 1;
+        let res = self.step();
+        if res.is_err(){
+            // Stops after the first error.
+            self.sv_idx = self.item.start_vertex.len();
             self.idx = usize::MAX;
         }
-        Some(ret)
+        Some(res)
     }
-}
\ No newline at end of file
+}